@@ -1,12 +1,17 @@
 use nurikabe::*;
 
 fn main() -> std::io::Result<()> {
-    let fs = std::fs::read_to_string("puzzles.b64l")?;
+    let collection = match PuzzleCollection::load("puzzles.b64l") {
+        Ok(collection) => collection,
+        Err(e) => {
+            eprintln!("puzzles.b64l: {}", e);
+            std::process::exit(1);
+        }
+    };
 
     let mut terminal = ratatui::init();
-    for line in fs.lines().map(|s| s.trim()) {
-        let board = Board::from_b64(line);
-        ui::menu::UI::solver(board).run(&mut terminal)?;
+    for puzzle in collection.iter() {
+        ui::menu::UI::solver(puzzle.board.clone()).run(&mut terminal)?;
     }
     ratatui::restore();
 
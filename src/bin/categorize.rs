@@ -2,11 +2,17 @@ use nurikabe::*;
 use std::io::*;
 
 fn main() -> Result<()> {
-    let fs = std::fs::read_to_string("puzzles.b64l")?;
+    let collection = match PuzzleCollection::load("puzzles.b64l") {
+        Ok(collection) => collection,
+        Err(e) => {
+            eprintln!("puzzles.b64l: {}", e);
+            std::process::exit(1);
+        }
+    };
     let mut file = std::fs::File::create("slugs.b64l")?;
 
-    for line in fs.lines().map(|s| s.trim()) {
-        writeln!(file, "{}", Board::from_b64(line).slug())?;
+    for puzzle in collection.iter() {
+        writeln!(file, "{}", puzzle.board.slug())?;
     }
 
     Ok(())
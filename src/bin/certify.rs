@@ -0,0 +1,43 @@
+use nurikabe::*;
+
+// Cross-checks solve against the SAT backend on every puzzle in a collection
+fn main() {
+    let path = std::env::args().nth(1).unwrap_or("puzzles.b64l".to_string());
+
+    let collection = match PuzzleCollection::load(&path) {
+        Ok(collection) => collection,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut disagreements = 0;
+    for (i, puzzle) in collection.iter().enumerate() {
+        let solution = solve(&puzzle.board);
+        let certificate = certify(&puzzle.board);
+
+        if certificate.agrees_with(&solution) {
+            continue;
+        }
+        disagreements += 1;
+
+        let verdict = match certificate {
+            Certificate::Unsolvable => "unsolvable",
+            Certificate::Unique(_) => "unique",
+            Certificate::NotUnique(..) => "not unique",
+        };
+
+        println!(
+            "{} ({}): SAT says {}, solve says solved={} unique={:?} contradiction={}",
+            puzzle.id.clone().unwrap_or(i.to_string()),
+            puzzle.board.b64(),
+            verdict,
+            solution.solved,
+            solution.unique,
+            solution.contradiction,
+        );
+    }
+
+    println!("{} of {} puzzles disagree", disagreements, collection.puzzles.len());
+}
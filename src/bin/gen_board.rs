@@ -27,6 +27,10 @@ fn main() -> std::io::Result<()> {
     ratatui::restore();
 
     println!("TTG: {}s", end.duration_since(start).as_secs_f32());
+    match board.pzpr() {
+        Ok(url) => println!("{}", url),
+        Err(e) => eprintln!("{}", e),
+    }
 
     Ok(())
 }
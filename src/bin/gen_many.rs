@@ -14,9 +14,13 @@ fn main() -> Result<()> {
         max_amends: 4,
     };
 
+    // The SAT check is slow, so only run it when asked
+    let check = std::env::args().any(|a| a == "--certify");
+
     let mut file = std::fs::File::create("puzzles7.b64l")?;
+    let mut seen = Set::default();
 
-    for _ in 0..500 {
+    for i in 0..500 {
         let mut num = 0;
         let start = std::time::Instant::now();
         let board = loop {
@@ -28,10 +32,24 @@ fn main() -> Result<()> {
         let end = std::time::Instant::now();
         dbg!(num);
 
+        if !seen.insert(board.canonical_slug()) {
+            println!("Duplicate puzzle, skipping");
+            continue;
+        }
+
         let soln = solve(&board);
-        if soln.solved && soln.unique {
-            assert!(board == Board::from_b64(&board.b64()));
-            writeln!(file, "{}", board.b64())?;
+        if soln.solved
+            && soln.unique == Some(true)
+            && (!check || certify(&board).agrees_with(&soln))
+        {
+            assert!(Board::from_b64(&board.b64()).is_ok_and(|b| b == board));
+
+            let mut puzzle = Puzzle::new(board);
+            puzzle.id = Some(i.to_string());
+            puzzle.source = Some("gen_many".to_string());
+            puzzle.settings = Some(format!("{:?}", settings));
+            puzzle.record(&soln);
+            writeln!(file, "{}", puzzle)?;
         }
 
         println!("TTG: {}s", end.duration_since(start).as_secs_f32());
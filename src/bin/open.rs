@@ -0,0 +1,29 @@
+use nurikabe::*;
+
+fn main() -> std::io::Result<()> {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("Usage: open <puzzle.txt | puzz.link URL>");
+        std::process::exit(1);
+    };
+
+    let board = if path.contains("?nurikabe") {
+        Board::from_pzpr(&path).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        })
+    } else {
+        let text = std::fs::read_to_string(&path)?;
+        text.parse().unwrap_or_else(|e| {
+            eprintln!("{}:{}", path, e);
+            std::process::exit(1);
+        })
+    };
+
+    let mut terminal = ratatui::init();
+
+    ui::menu::UI::solver(board).run(&mut terminal)?;
+
+    ratatui::restore();
+
+    Ok(())
+}
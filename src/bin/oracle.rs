@@ -0,0 +1,57 @@
+use nurikabe::*;
+
+// Tests solve against brute_force on random small puzzles. Any disagreement other than solve
+// giving up points at an unsound rule
+fn main() {
+    let count = std::env::args()
+        .nth(1)
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1000);
+
+    let settings = IslandGenSettings {
+        dims: (6, 6),
+        max_island_size: 6,
+        branch_factor: 1,
+        max_island_count: 12,
+    };
+
+    let (mut tested, mut gave_up, mut wrong) = (0, 0, 0);
+    while tested < count {
+        let Some(board) = try_generate(settings) else {
+            continue;
+        };
+        tested += 1;
+
+        let solution = solve(&board);
+        let solutions = brute_force(&board);
+
+        if !solution.solved && !solution.contradiction {
+            gave_up += 1;
+            continue;
+        }
+
+        let last = solution.states.last().unwrap();
+        let agrees = match solutions.len() {
+            0 => solution.contradiction,
+            1 => solution.solved && solution.unique == Some(true) && *last == solutions[0],
+            _ => solution.solved && solution.unique == Some(false) && solutions.contains(last),
+        };
+
+        if !agrees {
+            wrong += 1;
+            println!(
+                "{}: {} solutions, but solve says solved={} unique={:?} contradiction={}",
+                board.b64(),
+                solutions.len(),
+                solution.solved,
+                solution.unique,
+                solution.contradiction,
+            );
+        }
+    }
+
+    println!(
+        "{} puzzles: {} disagreements, solve gave up on {}",
+        tested, wrong, gave_up
+    );
+}
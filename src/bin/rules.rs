@@ -0,0 +1,5 @@
+use nurikabe::*;
+
+fn main() {
+    print!("{}", SolverConfig::default());
+}
@@ -0,0 +1,215 @@
+//! Compact base64 encoding, as used in `.b64l` files.
+//!
+//! The original (v0) layout stores dimensions, coordinates and clues as single bytes and the
+//! tile counts as big-endian `u16`s. Newer layouts start with a `0` byte (which would be a board
+//! of height 0 in v0) followed by a version byte. v1 has the same fields as v0, but stores every
+//! number as an LEB128 varint, so it has no size limits.
+
+use std::fmt;
+
+use base64::{DecodeError, Engine, engine::general_purpose::STANDARD};
+
+use super::*;
+
+const VERSION: u8 = 1;
+
+// Bigger boards than this are taken to be corrupt rather than allocated
+const MAX_CELLS: usize = 1 << 20;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum B64Error {
+    Base64(DecodeError),
+    UnknownVersion(u8),
+    Truncated,
+    BadNumber,
+    BadDimensions(usize, usize), // Empty, or too big
+    ZeroClue(Coord),
+    OutOfBounds(Coord),
+    TrailingBytes,
+}
+
+// Widths of the (dimension, count, value) fields
+#[derive(Clone, Copy)]
+enum Field {
+    U8,
+    U16,
+    Var,
+}
+
+const V0: [Field; 3] = [Field::U8, Field::U16, Field::U8];
+const V1: [Field; 3] = [Field::Var, Field::Var, Field::Var];
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    p: usize,
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, B64Error> {
+        let b = *self.bytes.get(self.p).ok_or(B64Error::Truncated)?;
+        self.p += 1;
+        Ok(b)
+    }
+
+    fn read(&mut self, field: Field) -> Result<usize, B64Error> {
+        use Field::*;
+        match field {
+            U8 => Ok(self.byte()? as usize),
+            U16 => Ok(((self.byte()? as usize) << 8) + self.byte()? as usize),
+            Var => {
+                let mut n = 0usize;
+                for shift in (0..usize::BITS).step_by(7) {
+                    let b = self.byte()?;
+                    let bits = (b & 0x7f) as usize;
+                    if (bits << shift) >> shift != bits {
+                        return Err(B64Error::BadNumber);
+                    }
+                    n |= bits << shift;
+                    if b & 0x80 == 0 {
+                        return Ok(n);
+                    }
+                }
+                Err(B64Error::BadNumber)
+            }
+        }
+    }
+}
+
+impl Board {
+    pub fn from_b64(input: &str) -> Result<Self, B64Error> {
+        let bytes = STANDARD.decode(input).map_err(B64Error::Base64)?;
+        let mut reader = Reader {
+            bytes: &bytes,
+            p: 0,
+        };
+
+        let layout = if bytes.first() == Some(&0) {
+            reader.byte()?;
+            match reader.byte()? {
+                1 => V1,
+                v => return Err(B64Error::UnknownVersion(v)),
+            }
+        } else {
+            V0
+        };
+
+        let [dim, count, value] = layout;
+
+        let h = reader.read(dim)?;
+        let w = reader.read(dim)?;
+        if h == 0 || w == 0 || h.checked_mul(w).is_none_or(|cells| cells > MAX_CELLS) {
+            return Err(B64Error::BadDimensions(h, w));
+        }
+
+        let ni = reader.read(count)?;
+        let nl = reader.read(count)?;
+        let ns = reader.read(count)?;
+
+        let coord = |reader: &mut Reader| -> Result<Coord, B64Error> {
+            let c = (reader.read(value)?, reader.read(value)?);
+            if c.0 < h && c.1 < w {
+                Ok(c)
+            } else {
+                Err(B64Error::OutOfBounds(c))
+            }
+        };
+
+        let mut islands = vec![];
+        for _ in 0..ni {
+            let (r, c) = coord(&mut reader)?;
+            let n = reader.read(value)?;
+            if n == 0 {
+                return Err(B64Error::ZeroClue((r, c)));
+            }
+            islands.push(Island { r, c, n });
+        }
+
+        let mut board = Board::from_islands(h, w, islands.into_iter());
+
+        for _ in 0..nl {
+            let c = coord(&mut reader)?;
+            board[c] = Land;
+        }
+
+        for _ in 0..ns {
+            let c = coord(&mut reader)?;
+            board[c] = Water;
+        }
+
+        if reader.p != bytes.len() {
+            return Err(B64Error::TrailingBytes);
+        }
+
+        Ok(board)
+    }
+
+    pub fn b64(&self) -> String {
+        let mut ser: Vec<u8> = vec![0, VERSION];
+
+        let mut push = |mut n: usize| {
+            while n >= 0x80 {
+                ser.push((n as u8 & 0x7f) | 0x80);
+                n >>= 7;
+            }
+            ser.push(n as u8);
+        };
+
+        let (h, w) = self.dims();
+        push(h);
+        push(w);
+
+        let cells = |tile| self.iter().filter(move |&(_, t)| t == tile).map(|t| t.0);
+        let land: Vec<Coord> = cells(Land).collect();
+        let seas: Vec<Coord> = cells(Water).collect();
+
+        push(self.islands.len());
+        push(land.len());
+        push(seas.len());
+
+        for &Island { r, c, n } in self.islands.iter() {
+            push(r);
+            push(c);
+            push(n);
+        }
+
+        for (r, c) in land.into_iter().chain(seas) {
+            push(r);
+            push(c);
+        }
+
+        STANDARD.encode(ser)
+    }
+}
+
+impl TryFrom<&str> for Board {
+    type Error = B64Error;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Self::from_b64(input)
+    }
+}
+
+impl fmt::Display for B64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use B64Error::*;
+        match self {
+            Base64(e) => write!(f, "invalid base64: {}", e),
+            UnknownVersion(v) => write!(f, "unknown encoding version {}", v),
+            Truncated => write!(f, "input ends unexpectedly"),
+            BadNumber => write!(f, "number too large"),
+            BadDimensions(h, w) => write!(f, "invalid dimensions {}x{}", h, w),
+            ZeroClue((r, c)) => write!(f, "clue at ({}, {}) is 0", r, c),
+            OutOfBounds((r, c)) => write!(f, "cell ({}, {}) is outside the board", r, c),
+            TrailingBytes => write!(f, "unexpected data after board"),
+        }
+    }
+}
+
+impl std::error::Error for B64Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            B64Error::Base64(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! puzz.link / pzprv3 URLs, e.g. `https://puzz.link/p?nurikabe/5/5/m5n2l4g`.
+//!
+//! After the puzzle type come the width, the height, and then the clues in row-major order. Each
+//! clue is a lowercase hex number (`0`-`f`, or `-xx`, `+xxx`, `=xxx`, `%xxx` for larger ones, up
+//! to 12287) and each run of 1-20 clueless cells is one of the letters `g`-`z`. The body has to
+//! cover every cell. pzprv writes its `?` clue as `.`, which has no meaning here.
+
+use std::fmt;
+
+use super::*;
+
+const PREFIX: &str = "https://puzz.link/p?nurikabe";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PzprError {
+    NotNurikabe,
+    BadDimensions,
+    // Byte offset into the clue body
+    BadClue(usize),
+    UnknownClue(usize),
+    TooManyCells,
+    TooFewCells,
+    // A clue the encoder can't write: 0, or 12288 and up
+    Unencodable(usize),
+}
+
+impl Board {
+    pub fn from_pzpr(url: &str) -> Result<Self, PzprError> {
+        use PzprError::*;
+
+        let query = url.rsplit_once('?').map_or(url, |(_, q)| q);
+        let mut parts = query.split('/');
+
+        if parts.next() != Some("nurikabe") {
+            return Err(NotNurikabe);
+        }
+
+        // Skip pzprv's optional flag segments, which come before the dimensions
+        let mut parts = parts.skip_while(|p| p.parse::<usize>().is_err());
+
+        let mut dim = || -> Result<usize, PzprError> {
+            parts
+                .next()
+                .and_then(|p| p.parse().ok())
+                .filter(|&n| n > 0)
+                .ok_or(BadDimensions)
+        };
+
+        let w = dim()?;
+        let h = dim()?;
+        let body = parts.next().unwrap_or("");
+
+        let mut board = Board::empty(h, w);
+        let bytes = body.as_bytes();
+
+        let mut cell = 0;
+        let mut p = 0;
+        while p < bytes.len() {
+            let start = p;
+            let ch = bytes[p] as char;
+            p += 1;
+
+            let mut hex = |len: usize| -> Result<usize, PzprError> {
+                let digits = bytes.get(p..p + len).ok_or(BadClue(start))?;
+                p += len;
+                // Not from_str_radix, which would take a sign and uppercase digits
+                digits.iter().try_fold(0, |n, &d| match d {
+                    b'0'..=b'9' => Ok(n * 16 + (d - b'0') as usize),
+                    b'a'..=b'f' => Ok(n * 16 + (d - b'a' + 10) as usize),
+                    _ => Err(BadClue(start)),
+                })
+            };
+
+            let clue = match ch {
+                '0'..='9' | 'a'..='f' => Some(ch.to_digit(16).unwrap() as usize),
+                '-' => Some(hex(2)?),
+                '+' => Some(hex(3)?),
+                '=' => Some(hex(3)? + 4096),
+                '%' => Some(hex(3)? + 8192),
+                '.' => return Err(UnknownClue(start)),
+                'g'..='z' => {
+                    cell += ch.to_digit(36).unwrap() as usize - 16;
+                    None
+                }
+                _ => return Err(BadClue(start)),
+            };
+
+            if cell >= h * w {
+                return Err(TooManyCells);
+            }
+
+            // pzprv allows a 0 clue, but an island of size 0 is meaningless here
+            if let Some(n) = clue {
+                if n == 0 {
+                    return Err(BadClue(start));
+                }
+                board.add_island(Island {
+                    r: cell / w,
+                    c: cell % w,
+                    n,
+                });
+            }
+
+            cell += 1;
+        }
+
+        if cell < h * w {
+            return Err(TooFewCells);
+        }
+
+        Ok(board)
+    }
+
+    pub fn pzpr(&self) -> Result<String, PzprError> {
+        let (h, w) = self.dims();
+
+        let mut body = String::new();
+        let mut gap = 0;
+
+        for r in 0..h {
+            for c in 0..w {
+                let Some(Island { n, .. }) = self.lookup_island((r, c)) else {
+                    gap += 1;
+                    if gap == 20 {
+                        body.push('z');
+                        gap = 0;
+                    }
+                    continue;
+                };
+
+                if gap > 0 {
+                    body.push(char::from_digit(15 + gap, 36).unwrap());
+                    gap = 0;
+                }
+
+                let clue = match n {
+                    1..16 => format!("{:x}", n),
+                    16..256 => format!("-{:02x}", n),
+                    256..4096 => format!("+{:03x}", n),
+                    4096..8192 => format!("={:03x}", n - 4096),
+                    8192..12288 => format!("%{:03x}", n - 8192),
+                    _ => return Err(PzprError::Unencodable(n)),
+                };
+                body.push_str(&clue);
+            }
+        }
+
+        if gap > 0 {
+            body.push(char::from_digit(15 + gap, 36).unwrap());
+        }
+
+        Ok(format!("{}/{}/{}/{}", PREFIX, w, h, body))
+    }
+}
+
+impl fmt::Display for PzprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use PzprError::*;
+        match self {
+            NotNurikabe => write!(f, "not a nurikabe URL"),
+            BadDimensions => write!(f, "missing or invalid dimensions"),
+            BadClue(p) => write!(f, "invalid clue at offset {}", p),
+            UnknownClue(p) => write!(f, "unknown (`.`) clue at offset {}", p),
+            TooManyCells => write!(f, "clues run past the end of the board"),
+            TooFewCells => write!(f, "clues stop before the end of the board"),
+            Unencodable(n) => write!(f, "clue {} can't be written in a URL", n),
+        }
+    }
+}
+
+impl std::error::Error for PzprError {}
@@ -0,0 +1,76 @@
+use std::hash::{Hash, Hasher};
+
+use rustc_hash::FxHasher;
+
+use super::*;
+
+// The 8 symmetries of the square: 4 rotations, each optionally preceded by a mirror
+pub const SYMMETRIES: usize = 8;
+
+impl Board {
+    // Applies symmetry t (in 0..SYMMETRIES). 0 is the identity
+    pub fn transform(&self, t: usize) -> Board {
+        let dims = transform_dims(self.dims(), t);
+        let (h, w) = dims;
+
+        let mut out = Board::empty(h, w);
+        for (c, tile) in self.iter() {
+            out[transform_coord(self.dims(), t, c)] = tile;
+        }
+
+        out.islands = self
+            .islands
+            .iter()
+            .map(|&Island { r, c, n }| {
+                let (r, c) = transform_coord(self.dims(), t, (r, c));
+                Island { r, c, n }
+            })
+            .collect();
+        out.islands.sort();
+
+        out
+    }
+
+    // Unique representative among all symmetries and clue orderings
+    pub fn canonical(&self) -> Board {
+        (0..SYMMETRIES)
+            .map(|t| self.transform(t))
+            .min_by(|a, b| {
+                let key = |b: &Board| (b.dims, b.islands.clone(), b.tiles.clone());
+                key(a).cmp(&key(b))
+            })
+            .unwrap()
+    }
+
+    // Identifies the puzzle (clues only), regardless of symmetry or solving progress
+    pub fn canonical_slug(&self) -> String {
+        self.clues().canonical().b64()
+    }
+
+    pub fn canonical_hash(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        self.clues().canonical().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // The unsolved puzzle
+    pub fn clues(&self) -> Board {
+        let (h, w) = self.dims();
+        Board::from_islands(h, w, self.islands.iter().copied())
+    }
+}
+
+fn transform_dims((h, w): Coord, t: usize) -> Coord {
+    if t % 2 == 1 { (w, h) } else { (h, w) }
+}
+
+fn transform_coord((h, w): Coord, t: usize, (r, c): Coord) -> Coord {
+    let c = if t >= 4 { w - 1 - c } else { c };
+
+    match t % 4 {
+        0 => (r, c),
+        1 => (c, h - 1 - r),
+        2 => (h - 1 - r, w - 1 - c),
+        _ => (w - 1 - c, r),
+    }
+}
@@ -0,0 +1,206 @@
+//! Plain-text grid format.
+//!
+//! One line per row, one token per cell:
+//!
+//! - a positive number is a clue,
+//! - `.` is an unknown cell,
+//! - `#` is water,
+//! - `o` is land.
+//!
+//! If no row has whitespace between its cells, every character is its own cell, so `2..#` is a row
+//! of four cells. To write clues of 10 or more, separate the cells with whitespace instead:
+//! `12 . . #`. One such row switches the whole board to this form, as do rows of one token each
+//! which would otherwise come out ragged (a single column, such as `12` over `.`). A single column
+//! of nothing but equally wide clues is still read character by character. Blank lines and
+//! indentation are ignored.
+//!
+//! ```text
+//! 2.o.
+//! ##.3
+//! ....
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use super::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBoardError {
+    // Both 1-indexed
+    pub line: usize,
+    pub col: usize,
+    pub kind: ParseBoardErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseBoardErrorKind {
+    NoRows,
+    BadCell(String),
+    ZeroClue,
+    RaggedRow { expected: usize, found: usize },
+}
+
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spaced = s.lines().any(|l| l.trim().contains(char::is_whitespace));
+
+        match parse(s, spaced) {
+            Err(e) if !spaced && matches!(e.kind, ParseBoardErrorKind::RaggedRow { .. }) => {
+                parse(s, true).map_err(|_| e)
+            }
+            result => result,
+        }
+    }
+}
+
+fn parse(s: &str, spaced: bool) -> Result<Board, ParseBoardError> {
+    use ParseBoardErrorKind::*;
+
+    let mut rows: Vec<Vec<Tile>> = vec![];
+    let mut islands = vec![];
+
+    for (l, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let r = rows.len();
+        let mut row = vec![];
+
+        for (col, token) in tokens(line, spaced) {
+            let error = |kind| ParseBoardError {
+                line: l + 1,
+                col: col + 1,
+                kind,
+            };
+
+            let tile = match token {
+                "." => Empty,
+                "#" => Water,
+                "o" => Land,
+                _ => {
+                    let Ok(n) = token.parse::<usize>() else {
+                        return Err(error(BadCell(token.to_string())));
+                    };
+
+                    if n == 0 {
+                        return Err(error(ZeroClue));
+                    }
+
+                    islands.push(Island { r, c: row.len(), n });
+                    Land
+                }
+            };
+
+            row.push(tile);
+        }
+
+        if let Some(first) = rows.first()
+            && first.len() != row.len()
+        {
+            return Err(ParseBoardError {
+                line: l + 1,
+                col: 1,
+                kind: RaggedRow {
+                    expected: first.len(),
+                    found: row.len(),
+                },
+            });
+        }
+
+        rows.push(row);
+    }
+
+    if rows.is_empty() {
+        return Err(ParseBoardError {
+            line: 1,
+            col: 1,
+            kind: NoRows,
+        });
+    }
+
+    let (h, w) = (rows.len(), rows[0].len());
+    let mut board = Board::from_islands(h, w, islands.into_iter());
+    board.tiles = rows.concat();
+
+    Ok(board)
+}
+
+// Yields each cell token along with its (0-indexed, char) column
+fn tokens(line: &str, spaced: bool) -> Vec<(usize, &str)> {
+    let line = line.trim_end();
+
+    let mut out = vec![];
+    let mut start = None;
+
+    for (col, (i, ch)) in line.char_indices().enumerate() {
+        if ch.is_whitespace() {
+            if let Some((c, s)) = start.take() {
+                out.push((c, &line[s..i]));
+            }
+        } else if !spaced {
+            out.push((col, &line[i..i + ch.len_utf8()]));
+        } else if start.is_none() {
+            start = Some((col, i));
+        }
+    }
+
+    if let Some((c, s)) = start {
+        out.push((c, &line[s..]));
+    }
+
+    out
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .islands
+            .iter()
+            .map(|i| i.n.to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        for (r, row) in self.rows().enumerate() {
+            for (c, tile) in row.iter().enumerate() {
+                let cell = match (self.lookup_island((r, c)), tile) {
+                    (Some(Island { n, .. }), _) => n.to_string(),
+                    (None, Empty) => ".".to_string(),
+                    (None, Water) => "#".to_string(),
+                    (None, Land) => "o".to_string(),
+                };
+
+                if width == 1 {
+                    write!(f, "{}", cell)?;
+                } else {
+                    if c != 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{:>width$}", cell)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ParseBoardErrorKind::*;
+        write!(f, "{}:{}: ", self.line, self.col)?;
+        match &self.kind {
+            NoRows => write!(f, "board has no rows"),
+            BadCell(token) => write!(f, "unrecognized cell `{}`", token),
+            ZeroClue => write!(f, "clues must be positive"),
+            RaggedRow { expected, found } => {
+                write!(f, "row has {} cells, expected {}", found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseBoardError {}
@@ -0,0 +1,119 @@
+use crate::{Area, neighbors, surrounding};
+
+use super::*;
+
+// A broken rule, along with the cells that break it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    Pool(Area),
+    // Water cut off from the largest body of water
+    DisconnectedSea(Area),
+    NoClue(Area),
+    MultipleClues(Area),
+    WrongSize(Island, Area),
+}
+
+impl Violation {
+    pub fn cells(&self) -> &Area {
+        use Violation::*;
+        match self {
+            Pool(a) | DisconnectedSea(a) | NoClue(a) | MultipleClues(a) | WrongSize(_, a) => a,
+        }
+    }
+}
+
+impl Board {
+    // Rules broken by this board. On partial boards, only reports violations that no way of
+    // filling the empty cells could fix
+    pub fn violations(&self) -> Vec<Violation> {
+        use Violation::*;
+
+        let mut violations = vec![];
+        let (h, w) = self.dims();
+
+        for r in 0..h.saturating_sub(1) {
+            for c in 0..w.saturating_sub(1) {
+                let square = vec![(r, c), (r + 1, c), (r, c + 1), (r + 1, c + 1)];
+
+                if square.iter().all(|&c| self[c] == Water) {
+                    violations.push(Pool(square));
+                }
+            }
+        }
+
+        // Seas which could still be joined through empty cells count as connected
+        let mut seas: Vec<Area> = components(self, |t| t != Land)
+            .into_iter()
+            .map(|mut a| {
+                a.retain(|&c| self[c] == Water);
+                a
+            })
+            .filter(|a| !a.is_empty())
+            .collect();
+
+        if seas.len() > 1 {
+            seas.sort_by_key(|a| a.len());
+            seas.pop();
+            violations.extend(seas.into_iter().map(DisconnectedSea));
+        }
+
+        for island in components(self, |t| t == Land) {
+            let enclosed = !surrounding(self, &island)
+                .iter()
+                .any(|&c| self[c] == Empty);
+
+            let clues: Vec<Island> = island
+                .iter()
+                .filter_map(|&c| self.lookup_island(c))
+                .collect();
+
+            match &clues[..] {
+                [] if enclosed => violations.push(NoClue(island)),
+                [] => (),
+                &[clue] => {
+                    if island.len() > clue.n || (enclosed && island.len() < clue.n) {
+                        violations.push(WrongSize(clue, island));
+                    }
+                }
+                _ => violations.push(MultipleClues(island)),
+            }
+        }
+
+        violations
+    }
+
+    pub fn is_solution(&self) -> bool {
+        self.solved() && self.violations().is_empty()
+    }
+}
+
+fn components(board: &Board, include: impl Fn(Tile) -> bool) -> Vec<Area> {
+    let (h, w) = board.dims();
+    let mut visited = vec![false; h * w];
+    let mut out = vec![];
+
+    for (start, tile) in board.iter() {
+        let (r, c) = start;
+        if visited[r * w + c] || !include(tile) {
+            continue;
+        }
+
+        let mut component = vec![];
+        let mut stack = vec![start];
+
+        while let Some(coord) = stack.pop() {
+            let (r, c) = coord;
+            if visited[r * w + c] || !include(board[coord]) {
+                continue;
+            }
+            visited[r * w + c] = true;
+
+            component.push(coord);
+            stack.extend(neighbors(board, coord));
+        }
+
+        out.push(component);
+    }
+
+    out
+}
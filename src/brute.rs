@@ -0,0 +1,49 @@
+use super::*;
+
+// A reference solver for small boards, to test the rule engine against. It tries both colours for
+// every empty cell in turn, and only abandons a partial colouring once Board::violations says it
+// can't be completed, so every answer follows directly from the rules of the puzzle.
+
+pub const BRUTE_FORCE_MAX_CELLS: usize = 36;
+
+// Every solution of the board
+pub fn brute_force(board: &Board) -> Vec<Board> {
+    let (h, w) = board.dims();
+    assert!(
+        h * w <= BRUTE_FORCE_MAX_CELLS,
+        "brute_force is only meant for boards up to 6x6"
+    );
+
+    let empty: Area = board
+        .iter()
+        .filter(|&(_, t)| t == Empty)
+        .map(|(c, _)| c)
+        .collect();
+
+    let land = board.iter().filter(|&(_, t)| t == Land).count();
+    let target = board.islands.iter().map(|i| i.n).sum();
+
+    let mut solutions = vec![];
+    fill(&mut board.clone(), &empty, land, target, &mut solutions);
+    solutions
+}
+
+fn fill(board: &mut Board, empty: &[Coord], land: usize, target: usize, out: &mut Vec<Board>) {
+    // Every land cell belongs to some island, so the amount of land is fixed by the clues
+    if land > target || land + empty.len() < target || !board.violations().is_empty() {
+        return;
+    }
+
+    let Some((&c, rest)) = empty.split_first() else {
+        out.push(board.clone());
+        return;
+    };
+
+    board[c] = Water;
+    fill(board, rest, land, target, out);
+
+    board[c] = Land;
+    fill(board, rest, land + 1, target, out);
+
+    board[c] = Empty;
+}
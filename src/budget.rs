@@ -0,0 +1,114 @@
+use std::{
+    fmt::Display,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::Instant,
+};
+
+use super::*;
+
+// Why a solve gave up before finishing
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stop {
+    TimeLimit,
+    StepLimit,
+    NodeLimit,
+    Cancelled,
+}
+
+impl Display for Stop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Stop::*;
+        let stop = match self {
+            TimeLimit => "time limit reached",
+            StepLimit => "step limit reached",
+            NodeLimit => "guess limit reached",
+            Cancelled => "cancelled",
+        };
+
+        write!(f, "{}", stop)
+    }
+}
+
+// Lets another thread stop a solve (see SolverConfig::cancel_with)
+#[derive(Clone, Default, Debug)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// How much work a solve may still do. Shared by every branch and thread of the solve, so the
+// limits are on the total
+#[derive(Default, Debug)]
+pub struct Budget {
+    deadline: Option<Instant>,
+    max_steps: Option<usize>,
+    max_nodes: Option<usize>,
+    cancel: Option<CancelToken>,
+    steps: AtomicUsize, // Deductions made, at any depth
+    nodes: AtomicUsize, // Hypotheses explored
+    stop: OnceLock<Stop>,
+}
+
+impl Budget {
+    pub fn new(config: &SolverConfig) -> Self {
+        Self {
+            deadline: config.time_limit.map(|limit| Instant::now() + limit),
+            max_steps: config.max_steps,
+            max_nodes: config.max_nodes,
+            cancel: config.cancel.clone(),
+            ..Default::default()
+        }
+    }
+
+    // Why the solve has to stop, if it does. Once a limit is hit, it stays hit
+    pub fn check(&self) -> Option<Stop> {
+        if let Some(&stop) = self.stop.get() {
+            return Some(stop);
+        }
+
+        let steps = self.steps.load(Ordering::Relaxed);
+        let nodes = self.nodes.load(Ordering::Relaxed);
+
+        let stop = if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+            Stop::Cancelled
+        } else if self.max_steps.is_some_and(|max| steps >= max) {
+            Stop::StepLimit
+        } else if self.max_nodes.is_some_and(|max| nodes >= max) {
+            Stop::NodeLimit
+        } else if self.deadline.is_some_and(|end| Instant::now() >= end) {
+            Stop::TimeLimit
+        } else {
+            return None;
+        };
+
+        Some(*self.stop.get_or_init(|| stop))
+    }
+
+    // The limit that was hit, without checking again
+    pub fn stopped(&self) -> Option<Stop> {
+        self.stop.get().copied()
+    }
+
+    pub fn step(&self) {
+        self.steps.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn node(&self) {
+        self.nodes.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,246 @@
+//! Line-oriented puzzle collections, extending the `.b64l` format.
+//!
+//! Each line is a base64 board (see [`Board::b64`]) optionally followed by tab-separated
+//! `key=value` fields: `id`, `source`, `settings`, `difficulty` (proof steps, as counted by
+//! [`Solution::steps`]), `time` (seconds to solve) and `solution` (a base64 board). In values,
+//! `%`, tabs and newlines are escaped as `%25`, `%09`, `%0A` and `%0D`. Plain `.b64l` files are
+//! valid collections with no metadata. Blank lines are ignored.
+
+use std::{fmt, fs, io, path::Path, str::FromStr};
+
+use super::*;
+
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    pub board: Board,
+    pub id: Option<String>,
+    pub source: Option<String>,
+    pub settings: Option<String>,
+    pub difficulty: Option<usize>,
+    pub time: Option<f32>,
+    pub solution: Option<Board>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PuzzleCollection {
+    pub puzzles: Vec<Puzzle>,
+}
+
+#[derive(Debug)]
+pub enum CollectionError {
+    Io(io::Error),
+    // 1-indexed line number
+    Line { line: usize, kind: LineError },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LineError {
+    BadBoard(B64Error),
+    BadSolution(B64Error),
+    MissingValue(String),
+    UnknownField(String),
+    BadValue(String),
+    BadEscape,
+}
+
+impl Puzzle {
+    pub fn new(board: Board) -> Self {
+        Self {
+            board,
+            id: None,
+            source: None,
+            settings: None,
+            difficulty: None,
+            time: None,
+            solution: None,
+        }
+    }
+
+    // Fills in difficulty, time and (if solved) solution
+    pub fn record(&mut self, solution: &Solution) {
+        self.difficulty = Some(solution.steps());
+        self.time = Some(solution.time);
+        if solution.solved {
+            self.solution = solution.states.last().cloned();
+        }
+    }
+}
+
+impl PuzzleCollection {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CollectionError> {
+        fs::read_to_string(path)
+            .map_err(CollectionError::Io)?
+            .parse()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Puzzle> {
+        self.puzzles.iter()
+    }
+
+    // Removes puzzles which are symmetries of an earlier one. Returns the number removed
+    pub fn dedup(&mut self) -> usize {
+        let before = self.puzzles.len();
+        let mut seen = Set::default();
+        self.puzzles.retain(|p| seen.insert(p.board.canonical_slug()));
+        before - self.puzzles.len()
+    }
+}
+
+impl FromStr for PuzzleCollection {
+    type Err = CollectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut puzzles = vec![];
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let puzzle = line
+                .parse()
+                .map_err(|kind| CollectionError::Line { line: i + 1, kind })?;
+            puzzles.push(puzzle);
+        }
+
+        Ok(Self { puzzles })
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = LineError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        use LineError::*;
+
+        let mut fields = line.trim().split('\t');
+        let board = Board::from_b64(fields.next().unwrap_or("")).map_err(BadBoard)?;
+        let mut puzzle = Puzzle::new(board);
+
+        for field in fields {
+            let Some((key, value)) = field.split_once('=') else {
+                return Err(MissingValue(field.to_string()));
+            };
+            let value = unescape(value)?;
+
+            let bad = || BadValue(key.to_string());
+            match key {
+                "id" => puzzle.id = Some(value),
+                "source" => puzzle.source = Some(value),
+                "settings" => puzzle.settings = Some(value),
+                "difficulty" => puzzle.difficulty = Some(value.parse().map_err(|_| bad())?),
+                "time" => puzzle.time = Some(value.parse().map_err(|_| bad())?),
+                "solution" => puzzle.solution = Some(Board::from_b64(&value).map_err(BadSolution)?),
+                _ => return Err(UnknownField(key.to_string())),
+            }
+        }
+
+        Ok(puzzle)
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board.b64())?;
+
+        let fields = [
+            ("id", self.id.clone()),
+            ("source", self.source.clone()),
+            ("settings", self.settings.clone()),
+            ("difficulty", self.difficulty.map(|d| d.to_string())),
+            ("time", self.time.map(|t| t.to_string())),
+            ("solution", self.solution.as_ref().map(Board::b64)),
+        ];
+
+        for (key, value) in fields {
+            if let Some(value) = value {
+                write!(f, "\t{}={}", key, escape(&value))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for PuzzleCollection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for puzzle in &self.puzzles {
+            writeln!(f, "{}", puzzle)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<Puzzle> for PuzzleCollection {
+    fn from_iter<T: IntoIterator<Item = Puzzle>>(iter: T) -> Self {
+        Self {
+            puzzles: iter.into_iter().collect(),
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::new();
+    for ch in value.chars() {
+        match ch {
+            '%' => out.push_str("%25"),
+            '\t' => out.push_str("%09"),
+            '\n' => out.push_str("%0A"),
+            '\r' => out.push_str("%0D"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn unescape(value: &str) -> Result<String, LineError> {
+    let mut out = String::new();
+    let mut parts = value.split('%');
+    out.push_str(parts.next().unwrap_or(""));
+
+    for part in parts {
+        let ch = match part.get(..2) {
+            Some("25") => '%',
+            Some("09") => '\t',
+            Some("0A") => '\n',
+            Some("0D") => '\r',
+            _ => return Err(LineError::BadEscape),
+        };
+        out.push(ch);
+        out.push_str(&part[2..]);
+    }
+
+    Ok(out)
+}
+
+impl fmt::Display for CollectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use LineError::*;
+        match self {
+            CollectionError::Io(e) => write!(f, "{}", e),
+            CollectionError::Line { line, kind } => {
+                write!(f, "line {}: ", line)?;
+                match kind {
+                    BadBoard(e) => write!(f, "invalid board: {}", e),
+                    BadSolution(e) => write!(f, "invalid solution: {}", e),
+                    MissingValue(field) => write!(f, "field `{}` has no value", field),
+                    UnknownField(key) => write!(f, "unknown field `{}`", key),
+                    BadValue(key) => write!(f, "invalid value for `{}`", key),
+                    BadEscape => write!(f, "invalid escape sequence"),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for CollectionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CollectionError::Io(e) => Some(e),
+            CollectionError::Line { .. } => None,
+        }
+    }
+}
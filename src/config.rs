@@ -0,0 +1,160 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use super::*;
+
+// How hard the solver tries. The default is what solve uses: every rule, in the order of RULES,
+// guessing one level deep at most
+#[derive(Clone)]
+pub struct SolverConfig {
+    pub rules: Vec<&'static dyn Rule>, // Enabled rules, in the order they're tried
+    pub max_depth: usize,
+    pub threads: usize,
+    pub stop_at_guess: bool, // Finish after the first step that needed a guess
+    pub check_unique: bool,  // If not, the first solution found is taken without looking for others
+    // Limits past which the solve gives up (see Budget)
+    pub time_limit: Option<Duration>,
+    pub max_steps: Option<usize>,
+    pub max_nodes: Option<usize>, // Hypotheses explored
+    pub cancel: Option<CancelToken>,
+    pub observer: Option<Arc<dyn SolverObserver>>,
+}
+
+impl SolverConfig {
+    pub fn new() -> Self {
+        Self {
+            rules: RULES.to_vec(),
+            max_depth: 1,
+            threads: 1,
+            stop_at_guess: false,
+            check_unique: true,
+            time_limit: None,
+            max_steps: None,
+            max_nodes: None,
+            cancel: None,
+            observer: None,
+        }
+    }
+
+    // Enable exactly these rules (by their names in RULES), tried in the order given. Without the
+    // contradiction rules, guesses can't be refuted, and wrong solutions can get through
+    pub fn rules(mut self, names: &[&str]) -> Result<Self, UnknownRule> {
+        self.rules = names
+            .iter()
+            .map(|&name| rule_named(name))
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
+    // Use exactly these rules, which can come from outside the crate
+    pub fn with_rules(mut self, rules: &[&'static dyn Rule]) -> Self {
+        self.rules = rules.to_vec();
+        self
+    }
+
+    // Add a rule, to be tried just before the one named
+    pub fn insert_before(
+        mut self,
+        name: &str,
+        rule: &'static dyn Rule,
+    ) -> Result<Self, UnknownRule> {
+        let i = self.position(name)?;
+        self.rules.insert(i, rule);
+        Ok(self)
+    }
+
+    pub fn without(mut self, name: &str) -> Result<Self, UnknownRule> {
+        let i = self.position(name)?;
+        self.rules.remove(i);
+        Ok(self)
+    }
+
+    fn position(&self, name: &str) -> Result<usize, UnknownRule> {
+        self.rules
+            .iter()
+            .position(|r| r.name() == name)
+            .ok_or_else(|| UnknownRule(name.to_string()))
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    pub fn stop_at_guess(mut self, stop: bool) -> Self {
+        self.stop_at_guess = stop;
+        self
+    }
+
+    pub fn check_unique(mut self, check: bool) -> Self {
+        self.check_unique = check;
+        self
+    }
+
+    pub fn time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    pub fn max_steps(mut self, max: usize) -> Self {
+        self.max_steps = Some(max);
+        self
+    }
+
+    pub fn max_nodes(mut self, max: usize) -> Self {
+        self.max_nodes = Some(max);
+        self
+    }
+
+    pub fn cancel_with(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    pub fn observe_with(mut self, observer: Arc<dyn SolverObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+}
+
+// The enabled rules, one per line, in the order they're tried
+impl fmt::Display for SolverConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, rule) in self.rules.iter().enumerate() {
+            let (name, cost, description) = (rule.name(), rule.cost(), rule.description());
+            writeln!(f, "{:2}. {} ({}): {}", i + 1, name, cost, description)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn rule_named(name: &str) -> Result<&'static dyn Rule, UnknownRule> {
+    RULES
+        .iter()
+        .find(|r| r.name() == name)
+        .copied()
+        .ok_or_else(|| UnknownRule(name.to_string()))
+}
+
+// A name that isn't one of RULES, or for insert_before and without, not one of the config's rules
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownRule(pub String);
+
+impl fmt::Display for UnknownRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no rule named `{}`", self.0)
+    }
+}
+
+impl std::error::Error for UnknownRule {}
@@ -0,0 +1,89 @@
+use super::*;
+
+// Splits the undecided cells of the board into regions which can be guessed in separately. Cells
+// touching each other (corners included, since pools span them) or sharing a possible island end
+// up in the same region, along with the clues of those islands. Once the known water is all one
+// sea, water in one region can't connect or cut off anything in another, so a guess in one
+// region says nothing about the rest.
+pub fn independent_regions(known: &Knowledge) -> Vec<Area> {
+    let board = known.board();
+    let (h, w) = board.dims();
+    let id = |(r, c): Coord| r * w + c;
+
+    let open: Vec<bool> = known.possibilities().map(|s| s.len() > 1).collect();
+    let undecided: Area = board
+        .iter()
+        .map(|(c, _)| c)
+        .filter(|&c| open[id(c)])
+        .collect();
+    if undecided.is_empty() {
+        return vec![];
+    }
+
+    // Water which is cut off from the rest could be joined up through any region, which ties
+    // them all together. So does having no water yet, as whatever water each region gets has to
+    // join up with the others'
+    let water = board.iter().filter(|&(_, t)| t == Water).count();
+    let one_sea = board
+        .iter()
+        .find(|&(_, t)| t == Water)
+        .is_some_and(|(sea, _)| area(&board, sea).len() == water);
+    if !one_sea {
+        return vec![undecided];
+    }
+
+    // Union-find over cells, followed by one node per island
+    let islands = known.island_set();
+    let node: Map<Island, usize> = islands
+        .iter()
+        .enumerate()
+        .map(|(i, &is)| (is, h * w + i))
+        .collect();
+    let mut parent: Vec<usize> = (0..h * w + islands.len()).collect();
+
+    fn find(parent: &mut [usize], mut x: usize) -> usize {
+        while parent[x] != x {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        x
+    }
+
+    fn union(parent: &mut [usize], a: usize, b: usize) {
+        let (a, b) = (find(parent, a), find(parent, b));
+        parent[a] = b;
+    }
+
+    for &c in &undecided {
+        for n in all_neighbors(&board, c) {
+            if open[id(n)] {
+                union(&mut parent, id(c), id(n));
+            }
+        }
+        for i in known.get(c).islands() {
+            union(&mut parent, id(c), node[&i]);
+        }
+    }
+
+    // Number regions in the order their first cell appears
+    let mut regions: Vec<Area> = vec![];
+    let mut numbering: Map<usize, usize> = Map::default();
+    for c in undecided {
+        let root = find(&mut parent, id(c));
+        let next = regions.len();
+        let r = *numbering.entry(root).or_insert(next);
+        if r == next {
+            regions.push(vec![]);
+        }
+        regions[r].push(c);
+    }
+
+    for &is in islands {
+        let root = find(&mut parent, node[&is]);
+        if let Some(&r) = numbering.get(&root) {
+            regions[r].push((is.r, is.c));
+        }
+    }
+
+    regions
+}
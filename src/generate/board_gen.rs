@@ -5,7 +5,7 @@ use rand::{
 
 use super::*;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct BoardGenSettings {
     pub dims: (usize, usize),
     pub mean_island_size: usize,
@@ -22,27 +22,29 @@ const FIXABLE_FRAC: f64 = 0.05;
 pub fn gen_board(settings: BoardGenSettings) -> Option<Board> {
     let board = gen_unlabelled(settings)?;
 
-    let labelled = metropolis_label(&board, settings);
+    // Clues are placed using only what can be deduced without guessing
+    let config = SolverConfig::new().max_depth(settings.max_depth);
+    let labelled = metropolis_label(&board, settings, &config.clone().max_depth(0));
 
     if empty_frac(&labelled) < FIXABLE_FRAC {
         return None;
     }
 
-    amend(&labelled, settings)
+    amend(&labelled, settings, &config)
 }
 
-pub fn amend(board: &Board, settings: BoardGenSettings) -> Option<Board> {
+pub fn amend(board: &Board, settings: BoardGenSettings, config: &SolverConfig) -> Option<Board> {
     let (h, w) = board.dims();
 
     let mut out = Board::from_islands(h, w, board.islands.iter().copied());
-    let mut soln = solve_with_limits(board, settings.max_depth);
+    let mut soln = solve_with(board, config);
 
     for _ in 0..settings.max_amends {
         if soln.contradiction {
             return None;
         }
 
-        if soln.solved && soln.unique {
+        if soln.solved && soln.unique == Some(true) {
             return Some(out);
         }
 
@@ -56,13 +58,13 @@ pub fn amend(board: &Board, settings: BoardGenSettings) -> Option<Board> {
             if area.len() < is.n {
                 is.n += 1;
                 out = Board::from_islands(h, w, out.islands.into_iter());
-                soln = solve_with_limits(&out, settings.max_depth);
+                soln = solve_with(&out, config);
                 break;
             }
         }
     }
 
-    if soln.solved && soln.unique {
+    if soln.solved && soln.unique == Some(true) {
         Some(out)
     } else {
         None
@@ -148,7 +150,7 @@ pub fn try_label(board: &Board, settings: BoardGenSettings) -> Option<Board> {
     for _ in 0..settings.label_attempts {
         let solution = solve_with_limits(&trial, settings.max_depth);
 
-        if solution.solved && solution.unique {
+        if solution.solved && solution.unique == Some(true) {
             return Some(trial);
         }
 
@@ -2,9 +2,9 @@ use super::*;
 use rand::seq::IndexedRandom;
 
 // Labels board using MH algorithm
-pub fn metropolis_label(board: &Board, settings: BoardGenSettings) -> Board {
+pub fn metropolis_label(board: &Board, settings: BoardGenSettings, config: &SolverConfig) -> Board {
     let mut curr = label_randomly(board);
-    let mut forced = solve_with_limits(&curr, 0).forced_board().clone();
+    let mut forced = solve_with(&curr, config).forced_board().clone();
     let mut score = score_board(&forced);
 
     let mut best = forced.clone();
@@ -20,11 +20,11 @@ pub fn metropolis_label(board: &Board, settings: BoardGenSettings) -> Board {
             continue;
         }
 
-        let prop_soln = solve_with_limits(&prop, 0);
+        let prop_soln = solve_with(&prop, config);
         let prop_forced = prop_soln.forced_board().clone();
         let prop_score = score_board(&prop_forced);
 
-        if prop_soln.solved && prop_soln.unique {
+        if prop_soln.solved && prop_soln.unique == Some(true) {
             return prop_forced;
         }
 
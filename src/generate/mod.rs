@@ -74,12 +74,12 @@ fn monotonic(board: &mut Board) -> bool {
     let mut known = Knowledge::new(board);
 
     'solve: loop {
-        let board = known.board();
+        let board = known.snapshot();
 
         for rule in MONOTONIC {
             use ReasonKind::*;
 
-            rule(&mut known, &board);
+            rule.apply(&mut known, &board);
             let reason = known.take_reason();
 
             match reason {
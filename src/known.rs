@@ -1,34 +1,100 @@
+use std::{
+    collections::VecDeque,
+    hash::{Hash, Hasher},
+    ops::{BitOr, BitOrAssign},
+    sync::{Arc, Mutex},
+};
+
 use super::*;
 
 pub use rustc_hash::FxHashMap as Map;
 pub use rustc_hash::FxHashSet as Set;
 
+// Roughly how many words of knowledge state the outcome cache may hold. Past that, the oldest
+// outcomes are forgotten first
+const OUTCOME_CACHE_WORDS: usize = 1 << 22;
+
 #[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Possibility {
     Isle(Island),
     Sea,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub enum ReasonKind {
     MaxDepthReached,
+    Interrupted, // Out of budget, see Budget
     Contradiction,
     Loud(Reason),  // Display rule
     Quiet(Reason), // Knowledge updated, but don't display
     Nil,
 }
 
+// Kinds of change, used to decide which rules need to run again
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Changes(u8);
+
+impl Changes {
+    pub const NONE: Self = Self(0);
+    pub const LAND: Self = Self(1); // A cell became land
+    pub const WATER: Self = Self(2); // A cell became water
+    pub const ISLANDS: Self = Self(4); // A cell can no longer belong to some island
+    pub const DEPTH: Self = Self(8); // The depth limit was raised
+
+    pub const TILES: Self = Self::LAND.union(Self::WATER);
+    pub const ANY: Self = Self::TILES.union(Self::ISLANDS);
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for Changes {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for Changes {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = self.union(other);
+    }
+}
+
+// An undoable change, recorded while inside a bifurcation
+#[derive(Clone)]
+enum Change {
+    Word(usize, u64), // Previous value of possibilities[i]
+    PathsInserted(Island),
+    PathsChanged(Island, Vec<Area>, Vec<Area>), // Paths removed and added, each in order
+}
+
 #[derive(Clone)]
 pub struct Knowledge {
     pub unique: bool,
+    pub witnesses: Option<(Board, Board)>, // Two different solutions, once unique is false
     pub depth: usize,
     pub depth_limit: usize, // Current depth limit. This can be raised (using Iterative deepening) up to raise_max
     pub raise_max: Option<usize>,
     pub reason: ReasonKind, // Gets disabled when we make a new change
-    islands: Vec<Island>,
+    pub changes: Changes,   // Since last take_changes
+    pub config: Arc<SolverConfig>,
+    pub budget: Arc<Budget>,
+    index: Arc<IslandIndex>,
     dims: (usize, usize),
-    possibilities: Vec<Set<Possibility>>, // None = Sea
+    possibilities: Vec<u64>, // index.words() bits per cell, see IslandIndex
     island_paths: Map<Island, Vec<Area>>,
+    trail: Vec<Change>,
+    regions: Option<Regions>, // As of the last snapshot. None after a retraction
+    pending: Vec<Coord>,      // Cells whose tile changed since the last snapshot
+    focus: Option<Arc<Vec<bool>>>, // The independent region being guessed in, if any
+    outcomes: Arc<Mutex<OutcomeCache>>,
 }
 
 impl Knowledge {
@@ -37,43 +103,74 @@ impl Knowledge {
         use ReasonKind::*;
 
         let (h, w) = board.dims();
+        let index = Arc::new(IslandIndex::new(&board.islands));
+        let words = index.words();
 
-        let mut possibility_space = Set::from_iter(board.islands.iter().copied().map(Isle));
-        possibility_space.insert(Sea);
+        // Initially assume any island could reach any tile
+        let mut possibility_space = vec![0u64; words];
+        for b in 0..=board.islands.len() {
+            possibility_space[b / 64] |= 1 << (b % 64);
+        }
 
-        let mut possibilities = vec![possibility_space.clone(); w * h];
+        let mut possibilities = possibility_space.repeat(w * h);
 
-        for &Island { r, c, n } in board.islands.iter() {
-            let i = r * w + c;
-            possibilities[i] = [Isle((r, c, n).into())].into_iter().collect();
+        let only = |cell: &mut [u64], p: Possibility| {
+            let b = index.bit(p).unwrap();
+            cell.fill(0);
+            cell[b / 64] = 1 << (b % 64);
+        };
+
+        for &island in board.islands.iter() {
+            let i = island.r * w + island.c;
+            only(&mut possibilities[i * words..(i + 1) * words], Isle(island));
         }
 
         for ((r, c), t) in board.iter() {
             let i = r * w + c;
             if t == Water {
-                possibilities[i] = [Sea].into_iter().collect();
+                only(&mut possibilities[i * words..(i + 1) * words], Sea);
             } else if t == Land {
-                possibilities[i].remove(&Sea);
+                possibilities[i * words] &= !1;
             }
         }
+
         Self {
             depth: 0,
             depth_limit: 1,
             raise_max: Some(1),
             reason: Nil,
-            islands: board.islands.clone(),
-            // Initially assume any island could reach any tile
+            changes: Changes::NONE,
+            config: Default::default(),
+            budget: Default::default(),
+            index,
             dims: board.dims,
             possibilities,
             unique: true,
+            witnesses: None,
             island_paths: Default::default(),
+            trail: vec![],
+            regions: None,
+            pending: vec![],
+            focus: None,
+            outcomes: Default::default(),
         }
     }
 
+    pub fn with_config(board: &Board, config: &SolverConfig) -> Self {
+        let mut known = Self::new(board);
+
+        known.depth_limit = config.max_depth.min(known.depth_limit);
+        known.raise_max = Some(config.max_depth);
+        known.config = Arc::new(config.clone());
+        known.budget = Arc::new(Budget::new(config));
+
+        known
+    }
+
     pub fn board(&self) -> Board {
         let (h, w) = self.dims;
 
-        let mut board = Board::from_islands(h, w, self.islands.iter().copied());
+        let mut board = Board::from_islands(h, w, self.island_set().iter().copied());
 
         for r in 0..h {
             for c in 0..w {
@@ -86,16 +183,184 @@ impl Knowledge {
         board
     }
 
-    pub fn get_mut(&mut self, (r, c): Coord) -> &mut Set<Possibility> {
+    // Returns whether p was possible
+    fn remove(&mut self, (r, c): Coord, p: Possibility) -> bool {
+        let Some(b) = self.index.bit(p) else {
+            return false;
+        };
+
         let (_, w) = self.dims;
-        let i = r * w + c;
-        &mut self.possibilities[i]
+        let i = (r * w + c) * self.index.words() + b / 64;
+        let mask = 1 << (b % 64);
+
+        let word = self.possibilities[i];
+        if word & mask == 0 {
+            return false;
+        }
+
+        if self.depth > 0 {
+            self.trail.push(Change::Word(i, word));
+        }
+        self.possibilities[i] = word & !mask;
+        self.repath((r, c), p);
+
+        // Removing Sea makes the cell land. Removing an island makes it water if only Sea is left
+        if p == Possibility::Sea {
+            self.changes |= Changes::LAND;
+            self.pending.push((r, c));
+        } else {
+            self.changes |= Changes::ISLANDS;
+            if self.known_sea((r, c)) {
+                self.changes |= Changes::WATER;
+                self.pending.push((r, c));
+            }
+        }
+        true
+    }
+
+    // Keeps the cached paths as enumerate_island_paths would find them now that p is ruled out at
+    // c. Which paths a shape has depends on the cells it covers and the cells next to it, and every
+    // shape has to cover the land its island owns
+    fn repath(&mut self, c: Coord, p: Possibility) {
+        use Possibility::*;
+
+        if self.island_paths.is_empty() {
+            return;
+        }
+
+        let owner = match self.if_known(c) {
+            Some(Isle(i)) if self.known_land(c) => Some(i),
+            _ => None,
+        };
+
+        // c turning into land rules out the paths of any island running past it
+        let mut islands: Vec<Island> = match p {
+            Sea => self.island_paths.keys().copied().collect(),
+            Isle(i) => vec![i],
+        };
+        islands.extend(owner.filter(|o| !islands.contains(o)));
+
+        for i in islands {
+            self.repath_island(i, c, owner == Some(i));
+        }
+    }
+
+    fn repath_island(&mut self, island: Island, c: Coord, owned: bool) {
+        let Some(paths) = self.island_paths.get_mut(&island) else {
+            return;
+        };
+
+        let stale =
+            |p: &Area| (owned && !p.contains(&c)) || p.iter().any(|&q| q == c || next_to(q, c));
+        if !paths.iter().any(stale) {
+            return;
+        }
+
+        let (stale, kept): (Vec<Area>, Vec<Area>) =
+            std::mem::take(paths).into_iter().partition(stale);
+
+        let mut shapes: Vec<Area> = stale
+            .iter()
+            .filter(|p| !owned || p.contains(&c))
+            .map(|p| {
+                let mut shape = p.clone();
+                shape.sort();
+                shape
+            })
+            .collect();
+        shapes.sort();
+        shapes.dedup();
+
+        let mut fresh = vec![];
+        for shape in shapes.iter().filter(|s| self.fits(island, s)) {
+            shape_paths(self, island, shape, &mut fresh);
+        }
+        fresh.sort();
+
+        let paths = self.island_paths.get_mut(&island).unwrap();
+        *paths = merge(kept, fresh.clone());
+
+        if self.depth > 0 && stale != fresh {
+            self.trail.push(Change::PathsChanged(island, stale, fresh));
+        }
+    }
+
+    // Whether the island could still take this shape, which it could before the last change
+    fn fits(&self, island: Island, shape: &[Coord]) -> bool {
+        use Possibility::*;
+        shape.iter().all(|&c| {
+            self.get(c).contains(&Isle(island))
+                && adjacent(self.dims, c).all(|n| shape.contains(&n) || !self.known_land(n))
+        })
+    }
+
+    fn undo(&mut self, mark: usize) {
+        use Change::*;
+        while self.trail.len() > mark {
+            match self.trail.pop().unwrap() {
+                Word(i, word) => {
+                    self.possibilities[i] = word;
+                    self.regions = None;
+                }
+                PathsInserted(island) => {
+                    self.island_paths.remove(&island);
+                }
+                PathsChanged(island, removed, added) => {
+                    let paths = self.island_paths.get_mut(&island).unwrap();
+                    let kept = subtract(std::mem::take(paths), &added);
+                    *paths = merge(kept, removed);
+                }
+            }
+        }
+    }
+
+    // The current board, which rules are run against. Region queries answer for this board until
+    // the next snapshot
+    pub fn snapshot(&mut self) -> Board {
+        let board = self.board();
+
+        match &mut self.regions {
+            Some(regions) => {
+                for c in self.pending.drain(..) {
+                    regions.set(c, board[c]);
+                }
+            }
+            None => {
+                self.regions = Some(Regions::new(&board));
+                self.pending.clear();
+            }
+        }
+
+        board
+    }
+
+    fn regions(&mut self) -> &mut Regions {
+        if self.regions.is_none() {
+            self.snapshot();
+        }
+        self.regions.as_mut().unwrap()
+    }
+
+    // Same as area(&snapshot, c)
+    pub fn region(&mut self, c: Coord) -> Area {
+        self.regions().region(c).clone()
+    }
+
+    // Same as surrounding(&snapshot, &area(&snapshot, c))
+    pub fn region_border(&mut self, c: Coord) -> Area {
+        self.regions().border(c)
+    }
+
+    // Same as !noncontiguous_board(&snapshot)
+    pub fn sea_connected(&mut self) -> bool {
+        self.regions().sea_connected()
     }
 
-    pub fn get(&self, (r, c): Coord) -> &Set<Possibility> {
+    pub fn get(&self, (r, c): Coord) -> Possibilities<'_> {
         let (_, w) = self.dims;
+        let words = self.index.words();
         let i = r * w + c;
-        &self.possibilities[i]
+        Possibilities::new(&self.possibilities[i * words..(i + 1) * words], &self.index)
     }
 
     pub fn known_sea(&self, c: Coord) -> bool {
@@ -119,26 +384,20 @@ impl Knowledge {
         }
     }
 
-    pub fn if_known(&self, (r, c): Coord) -> Option<Possibility> {
-        let (_, w) = self.dims;
-        let i = r * w + c;
-        let is = &self.possibilities[i];
-        if is.len() == 1 {
-            is.iter().copied().next()
-        } else {
-            None
-        }
+    pub fn if_known(&self, c: Coord) -> Option<Possibility> {
+        let is = self.get(c);
+        if is.len() == 1 { is.iter().next() } else { None }
     }
 
     pub fn island_set(&self) -> &Vec<Island> {
-        &self.islands
+        self.index.islands()
     }
 
     pub fn set_island(&mut self, reason: Reason, c: Coord, i: Island) {
-        let islands = self.islands.clone();
-        self.set_land(reason, c);
+        let islands = self.island_set().clone();
+        self.set_land(reason.clone(), c);
         for i2 in islands.into_iter().filter(|&i2| i2 != i) {
-            self.elim_island(reason, c, i2);
+            self.elim_island(reason.clone(), c, i2);
         }
     }
 
@@ -147,20 +406,15 @@ impl Knowledge {
         use ReasonKind::*;
         let was_known = self.tile_known(c).is_some();
 
-        let possibilities = self.get_mut(c);
-        if !possibilities.remove(&Isle(i)) {
+        if !self.remove(c, Isle(i)) {
             return;
         }
-        if let Some(paths) = self.island_paths.get_mut(&i) {
-            paths.retain(|p| !p.contains(&c));
-        }
 
         if self.tile_known(c).is_some() && !was_known {
             self.reason.set(Loud(reason));
         } else {
             self.reason.set(Quiet(reason));
         }
-        self.reason;
     }
 
     // Acknowledge a contradiction
@@ -173,15 +427,7 @@ impl Knowledge {
         use ReasonKind::*;
         if !self.known_land(c) {
             self.reason = Loud(reason);
-            self.get_mut(c).remove(&Sea);
-
-            for p in self.get(c).clone() {
-                let Isle(i) = p else {
-                    continue;
-                };
-
-                self.island_paths.remove(&i);
-            }
+            self.remove(c, Sea);
         }
     }
 
@@ -192,52 +438,201 @@ impl Knowledge {
         if !self.known_sea(c) {
             self.reason = Loud(reason);
 
-            for p in self.get(c).clone() {
-                let Isle(i) = p else {
-                    continue;
-                };
-
-                if let Some(paths) = self.island_paths.get_mut(&i) {
-                    paths.retain(|p| !p.contains(&c));
-                }
+            let islands: Vec<_> = self.get(c).islands().collect();
+            for i in islands {
+                self.remove(c, Isle(i));
             }
+        }
+    }
+
+    // Record that the puzzle has more than one solution, keeping the first pair found as evidence
+    pub fn not_unique(&mut self, witnesses: (Board, Board)) {
+        self.unique = false;
+        self.witnesses.get_or_insert(witnesses);
+    }
 
-            self.get_mut(c).retain(|p| p == &Sea);
+    // Whether the solve has to stop (recording Interrupted if so)
+    pub fn out_of_budget(&mut self) -> bool {
+        let stop = self.budget.check().is_some();
+        if stop {
+            self.reason = ReasonKind::Interrupted;
         }
+        stop
     }
 
     pub fn raise_depth_limit(&mut self) {
         self.depth_limit += 1;
+        self.changes |= Changes::DEPTH;
+        self.observe(|o| o.depth_limit_raised(self.depth_limit));
     }
 
-    pub fn bifurcate(&mut self) -> Option<Self> {
+    // Tell the observer, if there is one. Without one, f (and whatever it would compute) is skipped
+    pub fn observe(&self, f: impl FnOnce(&dyn SolverObserver)) {
+        if let Some(observer) = &self.config.observer {
+            f(observer.as_ref());
+        }
+    }
+
+    // Runs f one level deeper, then rolls back every change it made. Returns None (and records
+    // MaxDepthReached or Interrupted) if we are already at the depth limit, or out of budget
+    pub fn bifurcate<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> Option<T> {
         use ReasonKind::*;
-        if self.depth < self.depth_limit {
-            let mut copy = self.clone();
-            copy.raise_max = Some(0);
-            copy.depth += 1;
-            Some(copy)
-        } else {
+        if self.depth >= self.depth_limit {
             self.reason = MaxDepthReached;
-            None
+            return None;
         }
+
+        if self.out_of_budget() {
+            return None;
+        }
+        self.budget.node();
+
+        let saved = (
+            self.reason.clone(),
+            self.changes,
+            self.unique,
+            self.witnesses.clone(),
+            self.depth_limit,
+            self.raise_max,
+        );
+        let mark = self.trail.len();
+
+        self.raise_max = Some(0);
+        self.depth += 1;
+        self.observe(|o| o.bifurcation_started(self.depth));
+
+        let out = f(self);
+
+        self.observe(|o| o.bifurcation_ended(self.depth));
+        self.undo(mark);
+        self.depth -= 1;
+        (
+            self.reason,
+            self.changes,
+            self.unique,
+            self.witnesses,
+            self.depth_limit,
+            self.raise_max,
+        ) = saved;
+
+        Some(out)
+    }
+
+    // Assume something, then solve as far as possible. The same hypothesis often gets tested
+    // from the same state more than once (e.g. after an unrelated deduction, or once the depth
+    // limit is raised), so outcomes are remembered, by the whole state they were found in. The
+    // cached island paths needn't be part of that: they're always what enumerating afresh gives
+    pub fn explore(&mut self, hypothesis: &Hypothesis) -> Option<Outcome> {
+        let hash = self.state_hash(hypothesis);
+
+        if self.depth < self.depth_limit {
+            let outcomes = self.outcomes.lock().unwrap();
+            let hit = outcomes.get(hash, |state| self.is_state(state, hypothesis));
+            if let Some(outcome) = hit.cloned() {
+                drop(outcomes);
+                // Nothing happens in between, but the bifurcation still counts
+                self.observe(|o| o.bifurcation_started(self.depth + 1));
+                self.observe(|o| o.bifurcation_ended(self.depth + 1));
+                return Some(outcome);
+            }
+        }
+
+        let outcome = self.bifurcate(|bif| {
+            hypothesis.assume(bif);
+            let solution = solve_knowing(bif);
+            Outcome {
+                reason: bif.reason.clone(),
+                solution: bif.solved().then(|| bif.board()),
+                unique: bif.unique,
+                witnesses: bif.witnesses.clone(),
+                steps: solution.steps(),
+            }
+        })?;
+
+        // Cut short, so it says nothing about the hypothesis
+        if self.out_of_budget() {
+            return None;
+        }
+
+        let state = State {
+            possibilities: self.possibilities.clone(),
+            depth_left: self.depth_limit - self.depth,
+            unique: self.unique,
+            focus: self.focus.clone(),
+            hypothesis: hypothesis.clone(),
+        };
+        let mut outcomes = self.outcomes.lock().unwrap();
+        outcomes.insert(hash, state, outcome.clone());
+
+        Some(outcome)
     }
 
-    pub fn possibilities(&self) -> &Vec<Set<Possibility>> {
-        &self.possibilities
+    // Hash of the state exploring the hypothesis would start from, without copying it
+    fn state_hash(&self, hypothesis: &Hypothesis) -> u64 {
+        let mut hasher = rustc_hash::FxHasher::default();
+        self.possibilities.hash(&mut hasher);
+        (self.depth_limit - self.depth).hash(&mut hasher);
+        self.unique.hash(&mut hasher);
+        self.focus.hash(&mut hasher);
+        hypothesis.hash(&mut hasher);
+        hasher.finish()
     }
 
+    fn is_state(&self, state: &State, hypothesis: &Hypothesis) -> bool {
+        state.depth_left == self.depth_limit - self.depth
+            && state.unique == self.unique
+            && state.focus == self.focus
+            && state.hypothesis == *hypothesis
+            && state.possibilities == self.possibilities
+    }
+
+    pub fn possibilities(&self) -> impl Iterator<Item = Possibilities<'_>> {
+        self.possibilities
+            .chunks(self.index.words())
+            .map(|words| Possibilities::new(words, &self.index))
+    }
+
+    // Whether every cell is known. While focused on a region, only its cells count
     pub fn solved(&self) -> bool {
         use ReasonKind::*;
 
+        let in_focus = |i| self.focus.as_ref().is_none_or(|focus| focus[i]);
+
         self.reason != Contradiction
             && self.reason != MaxDepthReached
-            && self.possibilities.iter().all(|s| s.len() == 1)
+            && self.reason != Interrupted
+            && self
+                .possibilities()
+                .enumerate()
+                .all(|(i, s)| s.len() == 1 || !in_focus(i))
+    }
+
+    // Restrict guessing to one of the board's independent regions (see independent_regions)
+    pub fn focus(&mut self, region: &Area) {
+        let (h, w) = self.dims;
+        let mut focus = vec![false; h * w];
+        for &(r, c) in region {
+            focus[r * w + c] = true;
+        }
+        self.focus = Some(Arc::new(focus));
+    }
+
+    pub fn unfocus(&mut self) {
+        self.focus = None;
+    }
+
+    pub fn focused(&self) -> bool {
+        self.focus.is_some()
+    }
+
+    pub fn in_focus(&self, (r, c): Coord) -> bool {
+        let (_, w) = self.dims;
+        self.focus.as_ref().is_none_or(|focus| focus[r * w + c])
     }
 
     pub fn take_reason(&mut self) -> ReasonKind {
         use ReasonKind::*;
-        let reason = self.reason;
+        let reason = self.reason.clone();
         match reason {
             Loud(_) | Quiet(_) => {
                 self.reason = Nil;
@@ -250,20 +645,141 @@ impl Knowledge {
         reason
     }
 
+    pub fn take_changes(&mut self) -> Changes {
+        std::mem::take(&mut self.changes)
+    }
+
     pub fn island_paths(&mut self, island: Island) -> &Vec<Area> {
         if !self.island_paths.contains_key(&island) {
-            let paths: Vec<_> = enumerate_island_paths(self, island).collect();
+            let paths = enumerate_island_paths(self, island);
 
             self.island_paths.insert(island, paths);
+            if self.depth > 0 {
+                self.trail.push(Change::PathsInserted(island));
+            }
         }
         &self.island_paths[&island]
     }
+
+    // Number of paths the island could take, up to limit. Uses the cached paths if there are any
+    pub fn count_island_paths(&self, island: Island, limit: usize) -> usize {
+        match self.island_paths.get(&island) {
+            Some(paths) => paths.len().min(limit),
+            None => count_island_paths(self, island, limit),
+        }
+    }
+
+    pub fn dims(&self) -> Coord {
+        self.dims
+    }
+}
+
+// Both in order, as is the result
+fn merge(a: Vec<Area>, b: Vec<Area>) -> Vec<Area> {
+    let mut out = Vec::with_capacity(a.len() + b.len());
+    let (mut a, mut b) = (a.into_iter().peekable(), b.into_iter().peekable());
+    while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+        out.push(if x <= y { a.next() } else { b.next() }.unwrap());
+    }
+    out.extend(a);
+    out.extend(b);
+    out
+}
+
+// Takes one copy of each of paths out of all. Both in order, and paths has to be in all
+fn subtract(all: Vec<Area>, paths: &[Area]) -> Vec<Area> {
+    let mut paths = paths.iter().peekable();
+    all.into_iter()
+        .filter(|p| {
+            let taken = paths.peek() == Some(&p);
+            if taken {
+                paths.next();
+            }
+            !taken
+        })
+        .collect()
+}
+
+// Something to assume while exploring
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Hypothesis {
+    Land(Coord),
+    Sea(Coord),
+    Shape(Area), // Every cell is land
+}
+
+impl Hypothesis {
+    fn assume(&self, known: &mut Knowledge) {
+        match self {
+            Hypothesis::Land(c) => known.set_land(Reason::Bifurcation, *c),
+            Hypothesis::Sea(c) => known.set_sea(Reason::Bifurcation, *c),
+            Hypothesis::Shape(cells) => {
+                for &c in cells {
+                    known.set_land(Reason::Bifurcation, c);
+                }
+            }
+        }
+    }
+}
+
+// What happened under a hypothesis
+#[derive(Clone)]
+pub struct Outcome {
+    pub reason: ReasonKind,
+    pub solution: Option<Board>, // If solved
+    pub unique: bool,
+    pub witnesses: Option<(Board, Board)>,
+    pub steps: usize, // See Solution::steps
+}
+
+// Everything which decides how exploring a hypothesis goes, kept to check a cache hit against
+struct State {
+    possibilities: Vec<u64>,
+    depth_left: usize,
+    unique: bool,
+    focus: Option<Arc<Vec<bool>>>,
+    hypothesis: Hypothesis,
+}
+
+// Outcomes by the hash of their state. A hash can be shared, so the state is compared on a hit
+#[derive(Default)]
+struct OutcomeCache {
+    entries: Map<u64, Vec<(State, Outcome)>>,
+    order: VecDeque<u64>, // Hash of each entry, oldest first
+    words: usize,
+}
+
+impl OutcomeCache {
+    fn get(&self, hash: u64, is_state: impl Fn(&State) -> bool) -> Option<&Outcome> {
+        let entries = self.entries.get(&hash)?;
+        entries
+            .iter()
+            .find(|(state, _)| is_state(state))
+            .map(|(_, outcome)| outcome)
+    }
+
+    fn insert(&mut self, hash: u64, state: State, outcome: Outcome) {
+        self.words += state.possibilities.len();
+        self.entries.entry(hash).or_default().push((state, outcome));
+        self.order.push_back(hash);
+
+        while self.words > OUTCOME_CACHE_WORDS
+            && let Some(oldest) = self.order.pop_front()
+        {
+            let entries = self.entries.get_mut(&oldest).unwrap();
+            let (state, _) = entries.remove(0);
+            self.words -= state.possibilities.len();
+            if entries.is_empty() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
 }
 
 impl ReasonKind {
     pub fn set(&mut self, other: Self) {
         use ReasonKind::*;
-        match (*self, other) {
+        match (&*self, other) {
             (Quiet(_), Loud(r)) => *self = Loud(r),
             (Nil, r) => *self = r,
             _ => ()
@@ -272,6 +788,6 @@ impl ReasonKind {
 
     pub fn is_set(&self) -> bool {
         use ReasonKind::*;
-        matches!(self, Loud(_) | Quiet(_) | Contradiction)
+        matches!(self, Loud(_) | Quiet(_) | Contradiction | Interrupted)
     }
 }
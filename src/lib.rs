@@ -1,15 +1,37 @@
 pub mod board;
+pub mod brute;
+pub mod budget;
+pub mod collection;
+pub mod config;
+pub mod decompose;
 pub mod generate;
 pub mod known;
+pub mod observer;
+pub mod parallel;
+pub mod possibilities;
+pub mod regions;
 pub mod rules;
+pub mod sat;
+pub mod shapes;
 pub mod solve;
 pub mod ui;
 pub mod utils;
 
 pub use board::*;
+pub use brute::*;
+pub use budget::*;
+pub use collection::*;
+pub use config::*;
+pub use decompose::*;
 pub use generate::*;
 pub use known::*;
+pub use observer::*;
+pub use parallel::*;
+pub use possibilities::*;
+pub use regions::*;
 pub use rules::*;
+pub use sat::*;
+pub use shapes::*;
 pub use solve::*;
 pub use ui::*;
 pub use utils::*;
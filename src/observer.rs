@@ -0,0 +1,22 @@
+use super::*;
+
+// Gets told what the solver is doing as it goes (see SolverConfig::observe_with). Every method
+// does nothing by default. Hypotheses can be tested on several threads at once, so calls can come
+// from any of them
+pub trait SolverObserver: Send + Sync {
+    // A rule made a deduction. changed is the cells whose tile it decided, which can be none if it
+    // only ruled out some islands
+    fn step(&self, _rule: &dyn Rule, _reason: &Reason, _changed: &[Coord], _depth: usize) {}
+
+    fn contradiction(&self, _depth: usize) {}
+
+    fn depth_limit_raised(&self, _limit: usize) {}
+
+    // depth is that of the branch, so one more than the solve it came from
+    fn bifurcation_started(&self, _depth: usize) {}
+
+    fn bifurcation_ended(&self, _depth: usize) {}
+
+    // guess or island_contra found out where a hypothesis leads
+    fn tested(&self, _hypothesis: &Hypothesis, _outcome: &Outcome, _depth: usize) {}
+}
@@ -0,0 +1,121 @@
+use std::{
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use super::*;
+
+// Hypotheses are independent of each other, so at the top level they can be tested on several
+// threads at once (see SolverConfig::threads). Each thread works on its own copy of the
+// knowledge, and results are always reported in item order, so proofs don't depend on timing.
+
+// Whether first_some and test_until use threads here. Deeper down, hypotheses are already being
+// tested on one of them, so branches within a branch are always tested one at a time
+pub fn parallel(known: &Knowledge) -> bool {
+    known.config.threads > 1 && known.depth == 0
+}
+
+// The first item (in order) for which test gives an answer
+pub fn first_some<T: Sync, R: Send>(
+    known: &mut Knowledge,
+    items: &[T],
+    test: impl Fn(&mut Knowledge, &T) -> Option<R> + Sync,
+) -> Option<R> {
+    if !parallel(known) {
+        return items.iter().find_map(|item| test(known, item));
+    }
+
+    let next = AtomicUsize::new(0);
+    let best = AtomicUsize::new(usize::MAX);
+    let found = Mutex::new(vec![]);
+
+    let test = &test;
+    thread::scope(|s| {
+        for _ in 0..known.config.threads.min(items.len()) {
+            let mut known = known.clone();
+
+            let (next, best, found) = (&next, &best, &found);
+            s.spawn(move || {
+                loop {
+                    // Items before the best answer so far still have to be tested, in case they
+                    // give an earlier one. Items after it don't
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= items.len() || i > best.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    if let Some(r) = test(&mut known, &items[i]) {
+                        best.fetch_min(i, Ordering::Relaxed);
+                        found.lock().unwrap().push((i, r));
+                    }
+                }
+            });
+        }
+    });
+
+    found
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .min_by_key(|&(i, _)| i)
+        .map(|(_, r)| r)
+}
+
+// The result of test for every item, in order, up to the first result which settles things. Later
+// items aren't tested, or their results are dropped if they already were
+pub fn test_until<T: Sync, R: Send>(
+    known: &mut Knowledge,
+    items: &[T],
+    test: impl Fn(&mut Knowledge, &T) -> R + Sync,
+    settles: impl Fn(&R) -> bool + Sync,
+) -> Vec<R> {
+    if !parallel(known) {
+        let mut results = vec![];
+        for item in items {
+            let r = test(known, item);
+            let done = settles(&r);
+            results.push(r);
+            if done {
+                break;
+            }
+        }
+        return results;
+    }
+
+    let next = AtomicUsize::new(0);
+    let best = AtomicUsize::new(usize::MAX);
+    let results = Mutex::new(vec![]);
+
+    let (test, settles) = (&test, &settles);
+    thread::scope(|s| {
+        for _ in 0..known.config.threads.min(items.len()) {
+            let mut known = known.clone();
+
+            let (next, best, results) = (&next, &best, &results);
+            s.spawn(move || {
+                loop {
+                    // As in first_some, items after the earliest settling one can be skipped
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= items.len() || i > best.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    let r = test(&mut known, &items[i]);
+                    if settles(&r) {
+                        best.fetch_min(i, Ordering::Relaxed);
+                    }
+                    results.lock().unwrap().push((i, r));
+                }
+            });
+        }
+    });
+
+    let best = best.into_inner();
+    let mut results = results.into_inner().unwrap();
+    results.retain(|&(i, _)| i <= best);
+    results.sort_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
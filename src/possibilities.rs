@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use super::*;
+
+// Assigns each possibility a bit: bit 0 is Sea, bit i + 1 is the i-th island
+#[derive(Debug)]
+pub struct IslandIndex {
+    islands: Vec<Island>,
+    bits: Map<Island, usize>,
+    words: usize,
+}
+
+impl IslandIndex {
+    pub fn new(islands: &[Island]) -> Self {
+        let bits = islands
+            .iter()
+            .enumerate()
+            .map(|(i, &is)| (is, i + 1))
+            .collect();
+
+        Self {
+            islands: islands.to_vec(),
+            bits,
+            words: (islands.len() + 1).div_ceil(64),
+        }
+    }
+
+    pub fn islands(&self) -> &Vec<Island> {
+        &self.islands
+    }
+
+    // Number of u64s needed to store one cell's possibilities
+    pub fn words(&self) -> usize {
+        self.words
+    }
+
+    pub fn bit(&self, p: Possibility) -> Option<usize> {
+        use Possibility::*;
+        match p {
+            Sea => Some(0),
+            Isle(i) => self.bits.get(&i).copied(),
+        }
+    }
+
+    pub fn possibility(&self, bit: usize) -> Possibility {
+        use Possibility::*;
+        if bit == 0 {
+            Sea
+        } else {
+            Isle(self.islands[bit - 1])
+        }
+    }
+}
+
+// The possibilities for a single cell
+#[derive(Clone, Copy)]
+pub struct Possibilities<'a> {
+    words: &'a [u64],
+    index: &'a Arc<IslandIndex>,
+}
+
+impl<'a> Possibilities<'a> {
+    pub fn new(words: &'a [u64], index: &'a Arc<IslandIndex>) -> Self {
+        Self { words, index }
+    }
+
+    pub fn contains(&self, p: &Possibility) -> bool {
+        self.index
+            .bit(*p)
+            .is_some_and(|b| self.words[b / 64] & (1 << (b % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Possibility> + 'a {
+        let index = self.index;
+        self.words.iter().enumerate().flat_map(move |(i, &w)| {
+            let mut w = w;
+            std::iter::from_fn(move || {
+                if w == 0 {
+                    return None;
+                }
+                let b = w.trailing_zeros() as usize;
+                w &= w - 1;
+                Some(index.possibility(i * 64 + b))
+            })
+        })
+    }
+
+    pub fn islands(&self) -> impl Iterator<Item = Island> + 'a {
+        use Possibility::*;
+        self.iter()
+            .filter_map(|p| if let Isle(i) = p { Some(i) } else { None })
+    }
+
+    pub fn is_subset(&self, other: Possibilities) -> bool {
+        self.words
+            .iter()
+            .zip(other.words)
+            .all(|(a, b)| a & !b == 0)
+    }
+
+    pub fn to_set(&self) -> PossibilitySet {
+        PossibilitySet {
+            words: self.words.to_vec(),
+            index: self.index.clone(),
+        }
+    }
+
+    pub fn words(&self) -> &'a [u64] {
+        self.words
+    }
+}
+
+// Owned possibilities, e.g. for combining those of several cells
+#[derive(Clone)]
+pub struct PossibilitySet {
+    words: Vec<u64>,
+    index: Arc<IslandIndex>,
+}
+
+impl PossibilitySet {
+    pub fn view(&self) -> Possibilities<'_> {
+        Possibilities::new(&self.words, &self.index)
+    }
+
+    pub fn contains(&self, p: &Possibility) -> bool {
+        self.view().contains(p)
+    }
+
+    pub fn intersect(&mut self, other: Possibilities) {
+        for (a, b) in self.words.iter_mut().zip(other.words) {
+            *a &= b;
+        }
+    }
+}
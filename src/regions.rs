@@ -0,0 +1,174 @@
+use super::*;
+
+const NONE: usize = usize::MAX;
+
+// Connected regions of a board. Land and water regions only grow while solving, so they're kept
+// in a union-find. Empty regions split as cells get filled, so those (and the water + empty
+// regions used to check that the sea is connected) are labelled lazily, once per change.
+#[derive(Clone)]
+pub struct Regions {
+    board: Board,
+    parent: Vec<usize>,
+    members: Vec<Area>, // Indexed by root
+    empty: Option<Labels>,
+    open: Option<Labels>,
+}
+
+#[derive(Clone)]
+struct Labels {
+    label: Vec<usize>,
+    members: Vec<Area>,
+}
+
+impl Regions {
+    pub fn new(board: &Board) -> Self {
+        let (h, w) = board.dims();
+
+        let mut regions = Self {
+            board: Board::empty(h, w),
+            parent: (0..h * w).collect(),
+            members: vec![vec![]; h * w],
+            empty: None,
+            open: None,
+        };
+
+        for (c, t) in board.iter() {
+            regions.set(c, t);
+        }
+
+        regions
+    }
+
+    // Record that c is now tile. Only filling in an empty cell is incremental: anything else (as
+    // when a hypothesis is rolled back) rebuilds every region from scratch
+    pub fn set(&mut self, c: Coord, tile: Tile) {
+        let old = self.board[c];
+        if old == tile {
+            return;
+        }
+
+        // Cells only become un-empty while solving. Anything else is a retraction
+        if old != Empty {
+            let mut board = self.board.clone();
+            board[c] = tile;
+            *self = Self::new(&board);
+            return;
+        }
+
+        self.board[c] = tile;
+        self.empty = None;
+        if tile == Land {
+            self.open = None;
+        }
+
+        let i = self.id(c);
+        self.parent[i] = i;
+        self.members[i] = vec![c];
+
+        for n in neighbors(&self.board, c) {
+            if self.board[n] == tile {
+                self.union(i, self.id(n));
+            }
+        }
+    }
+
+    // Cells connected to c with the same tile, like utils::area
+    pub fn region(&mut self, c: Coord) -> &Area {
+        if self.board[c] == Empty {
+            let empty = self
+                .empty
+                .get_or_insert_with(|| label(&self.board, |t| t == Empty));
+            let i = empty.label[c.0 * self.board.dims.1 + c.1];
+            &empty.members[i]
+        } else {
+            let root = self.find(self.id(c));
+            &self.members[root]
+        }
+    }
+
+    // Neighbors of c's region outside it, like utils::surrounding. Cells bordering the region in
+    // several places are repeated
+    pub fn border(&mut self, c: Coord) -> Area {
+        let region = self.region(c).clone();
+        surrounding(&self.board, &region)
+    }
+
+    // Whether all water could still be joined up, like !utils::noncontiguous_board. The water and
+    // empty cells are flooded afresh after any cell becomes land, so this is only cheap while
+    // nothing but water is being added
+    pub fn sea_connected(&mut self) -> bool {
+        let open = self
+            .open
+            .get_or_insert_with(|| label(&self.board, |t| t != Land));
+
+        let mut seas = self
+            .board
+            .iter()
+            .filter(|&(_, t)| t == Water)
+            .map(|((r, c), _)| open.label[r * self.board.dims.1 + c]);
+
+        let Some(first) = seas.next() else {
+            return true;
+        };
+        seas.all(|l| l == first)
+    }
+
+    fn id(&self, (r, c): Coord) -> usize {
+        r * self.board.dims.1 + c
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+
+        if self.members[a].len() < self.members[b].len() {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parent[b] = a;
+        let moved = std::mem::take(&mut self.members[b]);
+        self.members[a].extend(moved);
+    }
+}
+
+fn label(board: &Board, include: impl Fn(Tile) -> bool) -> Labels {
+    let (_, w) = board.dims();
+    let mut label = vec![NONE; board.tiles.len()];
+    let mut members = vec![];
+
+    for (start, tile) in board.iter() {
+        if label[start.0 * w + start.1] != NONE || !include(tile) {
+            continue;
+        }
+
+        let l = members.len();
+        let mut region = vec![];
+        let mut stack = vec![start];
+        label[start.0 * w + start.1] = l;
+
+        while let Some(c) = stack.pop() {
+            region.push(c);
+            for n in neighbors(board, c) {
+                let i = n.0 * w + n.1;
+                if label[i] == NONE && include(board[n]) {
+                    label[i] = l;
+                    stack.push(n);
+                }
+            }
+        }
+
+        members.push(region);
+    }
+
+    Labels { label, members }
+}
@@ -16,7 +16,7 @@ pub fn all_paths_border(knowledge: &mut Knowledge, board: &Board) {
         for path in paths {
             let border = surrounding(board, &path);
 
-            let cells = HashSet::from_iter(border.into_iter());
+            let cells = HashSet::from_iter(border);
 
             intersection = intersection.intersection(&cells).copied().collect();
 
@@ -16,7 +16,7 @@ pub fn all_paths_intersect(knowledge: &mut Knowledge, _: &Board) {
         let mut intersection: HashSet<(usize, usize)> = paths[0].iter().copied().collect();
 
         for path in paths {
-            let cells = HashSet::from_iter(path.into_iter());
+            let cells = HashSet::from_iter(path);
 
             intersection = intersection.intersection(&cells).copied().collect();
 
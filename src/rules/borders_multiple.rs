@@ -7,12 +7,12 @@ pub fn borders_multiple(known: &mut Knowledge, board: &Board) {
             continue;
         }
 
-        let island = area(board, coord);
-        let surrounding = surrounding(board, &island);
+        let island = known.region(coord);
+        let surrounding = known.region_border(coord);
 
-        let mut possibilities = known.get(coord).clone();
+        let mut possibilities = known.get(coord).to_set();
         for &n in &island {
-            possibilities = possibilities.intersection(known.get(n)).copied().collect();
+            possibilities.intersect(known.get(n));
         }
 
         let island_set = known.island_set().clone();
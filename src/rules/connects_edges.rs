@@ -39,7 +39,7 @@ pub fn connects_edges(knowledge: &mut Knowledge, board: &Board) {
 
             grounded.insert(i, edge_is);
 
-            let area = area(board, coord);
+            let area = knowledge.region(coord);
             stack.extend(area.iter().flat_map(|&t| corners(board, t)));
         }
     };
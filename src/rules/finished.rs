@@ -11,10 +11,10 @@ pub fn finished(knowledge: &mut Knowledge, board: &Board) {
             continue;
         };
 
-        let area = area(board, coord);
+        let area = knowledge.region(coord);
 
         if area.len() == island.n {
-            for n in surrounding(board, &area) {
+            for n in knowledge.region_border(coord) {
                 knowledge.set_sea(Reason::Finished, n);
             }
 
@@ -2,25 +2,73 @@ use super::*;
 
 pub fn guess(known: &mut Knowledge, board: &Board) {
     use ReasonKind::*;
+
+    let regions = if known.focused() {
+        vec![]
+    } else {
+        independent_regions(known)
+    };
+
+    if regions.len() <= 1 {
+        if let Some(verdict) = guess_cells(known, board) {
+            verdict.apply(known);
+        }
+        return;
+    }
+
+    // Guess in each region on its own, so hypotheses (and the solutions they find) in one region
+    // don't drag the others along. A region with a solution (or two) only says the puzzle has one
+    // (or two) if no other region turns out to have none, so that waits until every region has
+    // been tried
+    let mut stuck = false;
+    let mut solved = None;
+    for region in regions {
+        known.focus(&region);
+        let verdict = guess_cells(known, board);
+        known.unfocus();
+
+        match verdict {
+            Some(verdict) if verdict.reason == Reason::Bifurcation => {
+                solved.get_or_insert(verdict);
+            }
+            Some(verdict) => return verdict.apply(known),
+            None if known.reason == MaxDepthReached => {
+                stuck = true;
+                if known.depth >= known.depth_limit {
+                    return;
+                }
+                known.reason = Nil;
+            }
+            None if known.reason.is_set() => return,
+            None => (),
+        }
+    }
+
+    if stuck {
+        known.reason = MaxDepthReached;
+    } else if let Some(verdict) = solved {
+        verdict.apply(known);
+    }
+}
+
+// What guessing found, if anything. MaxDepthReached is recorded if the region (or board) is left
+// undecided
+fn guess_cells(known: &mut Knowledge, board: &Board) -> Option<Verdict> {
+    use ReasonKind::*;
     let mut cells: Vec<_> = board
         .iter()
         .filter_map(|(c, t)| if t == Empty { Some(c) } else { None })
+        .filter(|&c| known.in_focus(c))
         .collect();
 
     // Long expression. It just counts the number of possibilities for this tile, with multiplicity for islands.
     cells.sort_by_key(|&c| {
         known
             .get(c)
-            .clone()
-            .iter()
-            .filter_map(|p| {
-                if let Possibility::Isle(i) = p {
-                    Some(i)
-                } else {
-                    None
-                }
-            })
-            .map(|&i| {
+            .islands()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|i| {
                 known
                     .island_paths(i)
                     .iter()
@@ -30,53 +78,82 @@ pub fn guess(known: &mut Knowledge, board: &Board) {
             .sum::<usize>()
     });
 
-    for c in cells {
-        let mut sol_found = false;
+    // Every hypothesis would go past the depth limit
+    if !cells.is_empty() && known.depth >= known.depth_limit {
+        known.reason = MaxDepthReached;
+        return None;
+    }
+
+    if let Some(verdict) = first_some(known, &cells, |known, &c| try_cell(known, c)) {
+        return Some(verdict);
+    }
 
-        let Some(mut bif) = known.bifurcate() else {
-            return;
-        };
+    if known.out_of_budget() {
+        return None;
+    }
 
-        bif.set_land(Reason::Bifurcation, c);
+    if !known.solved() {
+        known.reason = MaxDepthReached;
+    }
+    None
+}
 
-        let solution = solve_knowing(&mut bif);
+// What trying both colours of a cell showed: which colour it has to be, and why
+struct Verdict {
+    cell: Coord,
+    tile: Tile,
+    reason: Reason,
+    witnesses: Option<(Board, Board)>, // Two solutions, if both colours (or one of them twice) lead to one
+}
 
-        if bif.reason == Contradiction {
-            let len = solution.steps();
-            known.set_sea(Reason::ByContradiction(len), c);
-            if known.reason.is_set() {
-                return;
-            }
-        } else if bif.solved() {
-            if !bif.unique {
-                known.unique = false;
-                known.set_sea(Reason::Bifurcation, c);
-                return;
-            }
-            sol_found = true;
+impl Verdict {
+    fn apply(self, known: &mut Knowledge) {
+        if let Some(witnesses) = self.witnesses {
+            known.not_unique(witnesses);
         }
 
-        let Some(mut bif) = known.bifurcate() else {
-            return;
-        };
+        match self.tile {
+            Land => known.set_land(self.reason, self.cell),
+            _ => known.set_sea(self.reason, self.cell),
+        }
+    }
+}
 
-        bif.set_sea(Reason::Bifurcation, c);
+fn try_cell(known: &mut Knowledge, c: Coord) -> Option<Verdict> {
+    use ReasonKind::*;
+    let verdict = |tile, reason, witnesses| {
+        Some(Verdict {
+            cell: c,
+            tile,
+            reason,
+            witnesses,
+        })
+    };
 
-        let solution = solve_knowing(&mut bif);
-        if bif.reason == Contradiction {
-            let len = solution.steps();
-            known.set_land(Reason::ByContradiction(len), c);
-            if known.reason.is_set() {
-                return;
-            }
-        } else if bif.solved() && (sol_found || !bif.unique) {
-            known.unique = false;
-            known.set_land(Reason::Bifurcation, c);
-            return;
-        }
+    let hypothesis = Hypothesis::Land(c);
+    let land = known.explore(&hypothesis)?;
+    known.observe(|o| o.tested(&hypothesis, &land, known.depth));
+
+    if land.reason == Contradiction {
+        let len = land.steps;
+        return verdict(Water, Reason::ByContradiction(len), None);
+    } else if land.solution.is_some() && (!land.unique || !known.config.check_unique) {
+        // Carry on down a branch we know has a solution
+        return verdict(Land, Reason::Bifurcation, land.witnesses);
     }
 
-    if !known.solved() {
-        known.reason = MaxDepthReached;
+    let hypothesis = Hypothesis::Sea(c);
+    let sea = known.explore(&hypothesis)?;
+    known.observe(|o| o.tested(&hypothesis, &sea, known.depth));
+
+    if sea.reason == Contradiction {
+        let len = sea.steps;
+        return verdict(Land, Reason::ByContradiction(len), None);
+    } else if sea.solution.is_some() && land.solution.is_some() {
+        return verdict(Water, Reason::Bifurcation, land.solution.zip(sea.solution));
+    } else if sea.solution.is_some() && !sea.unique {
+        return verdict(Water, Reason::Bifurcation, sea.witnesses);
     }
+
+    None
 }
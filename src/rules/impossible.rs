@@ -1,7 +1,7 @@
 use super::*;
 
 pub fn impossible(known: &mut Knowledge, _: &Board) {
-    if known.possibilities().iter().any(|s| s.is_empty()) {
+    if known.possibilities().any(|s| s.is_empty()) {
         known.contradict();
     }
 }
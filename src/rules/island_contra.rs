@@ -6,6 +6,7 @@ const FEW: usize = 4;
 pub fn island_contra(known: &mut Knowledge, _: &Board) {
     use ReasonKind::*;
     let mut islands = known.island_set().clone();
+    islands.retain(|is| known.in_focus((is.r, is.c)));
     islands.sort_by_key(|&is| known.island_paths(is).len());
 
     for is in islands {
@@ -18,27 +19,62 @@ pub fn island_contra(known: &mut Knowledge, _: &Board) {
 
         let paths = paths.clone();
 
-        let mut contradictory = vec![None; n];
+        // Every hypothesis would go past the depth limit
+        if known.depth >= known.depth_limit {
+            known.reason = MaxDepthReached;
+            return;
+        }
 
-        for (i, path) in paths.iter().enumerate() {
-            let Some(mut bifurcation) = known.bifurcate() else {
-                return;
-            };
+        let test = |known: &mut Knowledge, path: &Area| {
+            let hypothesis = Hypothesis::Shape(path.clone());
+            let outcome = known.explore(&hypothesis)?;
+            known.observe(|o| o.tested(&hypothesis, &outcome, known.depth));
+            Some(outcome)
+        };
 
-            for &t in path {
-                bifurcation.set_land(Reason::Bifurcation, t);
+        // A shape settles it if it was cut short, or if it's taken below without looking at the
+        // rest. At the top level (see parallel), the shapes can be tested on several threads
+        let check_unique = known.config.check_unique;
+        let settles = |outcome: &Option<Outcome>| match outcome {
+            None => true,
+            Some(outcome) => {
+                outcome.solution.is_some() && (outcome.witnesses.is_some() || !check_unique)
             }
+        };
+        let outcomes = test_until(known, &paths, test, settles);
 
-            let solution = solve_knowing(&mut bifurcation);
+        if known.out_of_budget() {
+            return;
+        }
 
-            let prev_sol_found = contradictory[0..i].iter().any(|o| o.is_none());
+        let mut contradictory = vec![None; n];
+        let mut sol_found: Option<Board> = None;
+
+        for (i, (path, outcome)) in paths.iter().zip(outcomes).enumerate() {
+            let Some(outcome) = outcome else {
+                return;
+            };
 
-            if bifurcation.reason == Contradiction {
-                let len = solution.steps();
+            if outcome.reason == Contradiction {
+                let len = outcome.steps;
                 contradictory[i] = Some(len);
                 continue;
-            } else if bifurcation.solved() && (!bifurcation.unique || prev_sol_found) {
-                known.unique = false;
+            } else if let Some(solution) = outcome.solution {
+                // Take this shape if it's a second solution, if it has more than one of its own,
+                // or if we aren't looking for others
+                let witnesses = match (sol_found.take(), outcome.witnesses) {
+                    (Some(found), _) => Some((found, solution)),
+                    (None, Some(witnesses)) => Some(witnesses),
+                    (None, None) if known.config.check_unique => {
+                        sol_found = Some(solution);
+                        continue;
+                    }
+                    (None, None) => None,
+                };
+
+                if let Some(witnesses) = witnesses {
+                    known.not_unique(witnesses);
+                }
                 for &t in path {
                     known.set_land(Reason::Bifurcation, t);
                 }
@@ -47,11 +83,10 @@ pub fn island_contra(known: &mut Knowledge, _: &Board) {
         }
 
         // Find correct solution
-        let Some(sol) =
-            contradictory
-                .iter()
-                .enumerate()
-                .find_map(|(i, l)| if l.is_none() { Some(i) } else { None })
+        let Some(sol) = contradictory
+            .iter()
+            .enumerate()
+            .find_map(|(i, l)| if l.is_none() { Some(i) } else { None })
         else {
             // All result in a contradiction
             known.contradict();
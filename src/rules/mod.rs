@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display};
 
 use super::*;
 
@@ -46,55 +46,286 @@ use sea_complete::*;
 use sea_trapped::*;
 use wall_trick::*;
 
-pub type Rule = fn(&mut Knowledge, &Board);
+// A deduction technique. A rule looks at the board and either updates the knowledge (which sets a
+// reason for the step) or calls contradict. Rules are used as &'static dyn Rule, so techniques from
+// outside the crate can be added to a SolverConfig like the built-in ones
+pub trait Rule: Sync {
+    // Unique among the rules in a config, which picks rules by name
+    fn name(&self) -> &'static str;
 
-pub const RULES: &[Rule] = &[
-    // Contradiction rules
+    fn description(&self) -> &'static str;
+
+    // Roughly how hard the technique is to spot, added up in Solution::cost
+    fn cost(&self) -> usize;
+
+    fn apply(&self, known: &mut Knowledge, board: &Board);
+
+    // Changes which could make the rule apply again after it found nothing
+    fn wakes(&self) -> Changes {
+        Changes::ANY
+    }
+
+    // For rules without a variant of their own in Reason
+    fn reason(&self) -> Reason {
+        Reason::Custom(self.name().into())
+    }
+}
+
+// One of the rules in this crate, all of which are plain functions
+pub struct Builtin {
+    name: &'static str,
+    description: &'static str,
+    cost: usize,
+    wakes: Changes,
+    apply: fn(&mut Knowledge, &Board),
+}
+
+impl Rule for Builtin {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn cost(&self) -> usize {
+        self.cost
+    }
+
+    fn apply(&self, known: &mut Knowledge, board: &Board) {
+        (self.apply)(known, board)
+    }
+
+    fn wakes(&self) -> Changes {
+        self.wakes
+    }
+}
+
+const fn builtin(
+    name: &'static str,
+    description: &'static str,
+    cost: usize,
+    wakes: Changes,
+    apply: fn(&mut Knowledge, &Board),
+) -> Builtin {
+    Builtin {
+        name,
+        description,
+        cost,
+        wakes,
+        apply,
+    }
+}
+
+const ANY: Changes = Changes::ANY;
+const GUESSING: Changes = Changes::ANY.union(Changes::DEPTH);
+
+// Contradiction rules
+const POOLS: Builtin = builtin(
+    "pools",
+    "Water never covers a 2x2 square",
+    1,
+    Changes::WATER,
     pools,
+);
+const NONCONTIGUOUS: Builtin = builtin(
+    "noncontiguous",
+    "All the water is connected",
+    1,
+    Changes::TILES,
     noncontiguous,
+);
+const IMPOSSIBLE: Builtin = builtin(
+    "impossible",
+    "Every cell can be something",
+    1,
+    ANY,
     impossible,
+);
+const NO_SPACE: Builtin = builtin(
+    "no_space",
+    "Every island has room to grow",
+    2,
+    ANY,
     no_space,
+);
+const NO_GOOD_SPACE: Builtin = builtin(
+    "no_good_space",
+    "Every island has a shape that leaves the water connected",
+    3,
+    ANY,
     no_good_space,
-    // Deduction rules
+);
+
+// Deduction rules
+const SEA_COMPLETE: Builtin = builtin(
+    "sea_complete",
+    "Once there is as much water as there can be, the rest is land",
+    1,
+    Changes::WATER,
     sea_complete,
+);
+const FINISHED: Builtin = builtin(
+    "finished",
+    "Complete islands are surrounded by water",
+    1,
+    ANY,
     finished,
+);
+const CORNERED: Builtin = builtin(
+    "cornered",
+    "The last cell of a 2x2 square with three water cells is land",
+    1,
+    Changes::WATER,
     cornered,
+);
+const BORDERS_MULTIPLE: Builtin = builtin(
+    "borders_multiple",
+    "Land and the cells around it can only join islands the land could belong to",
+    1,
+    ANY,
     borders_multiple,
+);
+const ONE_WAY: Builtin = builtin(
+    "one_way",
+    "An island or body of water with one way out has to take it",
+    1,
+    Changes::TILES,
     one_way,
+);
+const TRAPPED: Builtin = builtin(
+    "trapped",
+    "Empty cells walled in by one island belong to it",
+    2,
+    ANY,
     trapped,
+);
+const CONNECTS_EDGES: Builtin = builtin(
+    "connects_edges",
+    "Land can't cut the water in two by joining edges of the board",
+    3,
+    ANY,
     connects_edges,
+);
+// Clues never move, and cells never become empty again
+const DISTANCE: Builtin = builtin(
+    "distance",
+    "Cells out of every island's reach are water",
+    1,
+    Changes::NONE,
     distance,
+);
+const REACHABILITY: Builtin = builtin(
+    "reachability",
+    "Islands can only own cells they can reach",
+    2,
+    ANY,
     reachability,
+);
+const ALL_PATHS_INTERSECT: Builtin = builtin(
+    "all_paths_intersect",
+    "A cell in every shape an island could take is part of it",
+    3,
+    ANY,
     all_paths_intersect,
+);
+const ALL_PATHS_BORDER: Builtin = builtin(
+    "all_paths_border",
+    "A cell next to every shape an island could take is water",
+    3,
+    ANY,
     all_paths_border,
+);
+const WALL_TRICK: Builtin = builtin(
+    "wall_trick",
+    "A cell only reachable through a gap in a wall of water",
+    3,
+    ANY,
     wall_trick,
+);
+const PRUNED_ALL_PATHS_INTERSECT: Builtin = builtin(
+    "pruned_all_paths_intersect",
+    "As all_paths_intersect, leaving out shapes which would break the board",
+    4,
+    ANY,
     pruned_all_paths_intersect,
+);
+const PRUNED_ALL_PATHS_BORDER: Builtin = builtin(
+    "pruned_all_paths_border",
+    "As all_paths_border, leaving out shapes which would break the board",
+    4,
+    ANY,
     pruned_all_paths_border,
+);
+const PRUNED_REACHABILITY: Builtin = builtin(
+    "pruned_reachability",
+    "As reachability, leaving out shapes which would break the board",
+    4,
+    ANY,
     pruned_reachability,
-    // Resort to trial & error
+);
+
+// Trial & error
+const ISLAND_CONTRA: Builtin = builtin(
+    "island_contra",
+    "Try each shape of an island with few of them",
+    8,
+    GUESSING,
     island_contra,
-    guess,
+);
+const GUESS: Builtin = builtin("guess", "Try both colours of a cell", 10, GUESSING, guess);
+
+// Every rule in this crate, in the order solve tries them
+pub const RULES: &[&dyn Rule] = &[
+    // Contradiction rules
+    &POOLS,
+    &NONCONTIGUOUS,
+    &IMPOSSIBLE,
+    &NO_SPACE,
+    &NO_GOOD_SPACE,
+    // Deduction rules
+    &SEA_COMPLETE,
+    &FINISHED,
+    &CORNERED,
+    &BORDERS_MULTIPLE,
+    &ONE_WAY,
+    &TRAPPED,
+    &CONNECTS_EDGES,
+    &DISTANCE,
+    &REACHABILITY,
+    &ALL_PATHS_INTERSECT,
+    &ALL_PATHS_BORDER,
+    &WALL_TRICK,
+    &PRUNED_ALL_PATHS_INTERSECT,
+    &PRUNED_ALL_PATHS_BORDER,
+    &PRUNED_REACHABILITY,
+    // Resort to trial & error
+    &ISLAND_CONTRA,
+    &GUESS,
 ];
 
-pub const MONOTONIC: &[Rule] = &[
+// Rules used by try_generate to check boards while clues are still being placed
+pub const MONOTONIC: &[&dyn Rule] = &[
     // Contradiction rules
-    pools,
-    noncontiguous,
-    no_space,
+    &POOLS,
+    &NONCONTIGUOUS,
+    &NO_SPACE,
     // Deduction rules
-    finished,
-    cornered,
-    borders_multiple,
-    one_way,
-    trapped,
-    connects_edges,
-    all_paths_intersect,
-    all_paths_border,
-    pruned_all_paths_intersect,
-    pruned_all_paths_border,
+    &FINISHED,
+    &CORNERED,
+    &BORDERS_MULTIPLE,
+    &ONE_WAY,
+    &TRAPPED,
+    &CONNECTS_EDGES,
+    &ALL_PATHS_INTERSECT,
+    &ALL_PATHS_BORDER,
+    &PRUNED_ALL_PATHS_INTERSECT,
+    &PRUNED_ALL_PATHS_BORDER,
 ];
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Reason {
     SeaComplete,
     ConnectsEdges,
@@ -110,6 +341,8 @@ pub enum Reason {
     AllPathsBorder,
     ByContradiction(usize),
     Bifurcation,
+    // From a rule outside the crate, see Rule::reason
+    Custom(Cow<'static, str>),
 }
 
 impl Display for Reason {
@@ -130,6 +363,7 @@ impl Display for Reason {
             SeaComplete => "Sea complete",
             ByContradiction(l) => return write!(f, "Contradiction in {} steps", l),
             Bifurcation => "Arbitrary",
+            Custom(reason) => reason,
         };
 
         write!(f, "{}", reason)
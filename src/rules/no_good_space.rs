@@ -1,10 +1,19 @@
+use std::ops::ControlFlow;
+
 use super::*;
 
 pub fn no_good_space(known: &mut Knowledge, board: &Board) {
     for &island in known.island_set() {
-        if !enumerate_island_paths(known, island)
-            .any(|path| !noncontiguous_board(&board_with(board, &path)))
-        {
+        let none_good = visit_island_shapes(known, island, |shape| {
+            if noncontiguous_board(&board_with(board, shape)) {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        })
+        .is_continue();
+
+        if none_good {
             known.contradict();
             return;
         }
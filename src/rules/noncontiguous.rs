@@ -1,8 +1,8 @@
 use super::*;
 
 // Checks that Sea+Empty tiles form a single connected component
-pub fn noncontiguous(known: &mut Knowledge, board: &Board) {
-    if noncontiguous_board(board) {
+pub fn noncontiguous(known: &mut Knowledge, _: &Board) {
+    if !known.sea_connected() {
         known.contradict();
     }
 }
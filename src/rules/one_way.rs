@@ -6,8 +6,7 @@ pub fn one_way(known: &mut Knowledge, board: &Board) {
             continue;
         }
 
-        let area = area(board, coord);
-        let surrounding = surrounding(board, &area);
+        let surrounding = known.region_border(coord);
 
         let mut empties = surrounding.iter().filter(|&&c| board[c] == Empty);
 
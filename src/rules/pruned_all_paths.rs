@@ -64,7 +64,7 @@ pub fn pruned_all_paths_border(knowledge: &mut Knowledge, board: &Board) {
 
         for path in &paths[1..] {
             let border = surrounding(board, path);
-            let cells = HashSet::from_iter(border.into_iter());
+            let cells = HashSet::from_iter(border);
 
             intersection = intersection.intersection(&cells).copied().collect();
 
@@ -86,5 +86,5 @@ pub fn pruned_all_paths_border(knowledge: &mut Knowledge, board: &Board) {
 }
 
 fn too_many_paths(known: &Knowledge, island: Island) -> bool {
-    enumerate_island_paths(known, island).take(LONG + 1).count() == LONG + 1
+    known.count_island_paths(island, LONG + 1) > LONG
 }
@@ -6,8 +6,8 @@ pub fn trapped(knowledge: &mut Knowledge, board: &Board) {
             continue;
         }
 
-        let area = area(board, coord);
-        let surrounding = surrounding(board, &area);
+        let area = knowledge.region(coord);
+        let surrounding = knowledge.region_border(coord);
 
         let trapped = board.iter().any(|(_, t)| t == Water)
             && surrounding.iter().all(|coord| board[*coord] == Land);
@@ -1,7 +1,6 @@
 use super::*;
 
 pub fn wall_trick(known: &mut Knowledge, board: &Board) {
-    use Possibility::*;
     for (c, _) in board.iter() {
         let flipped = [true, false];
         // # of 90 degree rotations
@@ -29,10 +28,10 @@ pub fn wall_trick(known: &mut Knowledge, board: &Board) {
 
                 let far_paths_all_pass = known
                     .get(far)
-                    .clone()
-                    .iter()
-                    .filter_map(|s| if let Isle(i) = s { Some(i) } else { None })
-                    .all(|&i| {
+                    .islands()
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .all(|i| {
                         known
                             .island_paths(i)
                             .iter()
@@ -0,0 +1,450 @@
+use std::ops::Not;
+
+// A small CDCL SAT solver: two watched literals, first-UIP clause learning, VSIDS branching with
+// phase saving, and Luby restarts. Clauses can be added between calls to solve, and everything
+// learned so far is kept, since the clause set only ever grows.
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Lit(u32);
+
+impl Lit {
+    pub fn new(var: usize, positive: bool) -> Self {
+        Self((var as u32) << 1 | u32::from(!positive))
+    }
+
+    pub fn var(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    pub fn positive(self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(self.0 ^ 1)
+    }
+}
+
+const RESTART_BASE: usize = 100;
+const DECAY: f64 = 0.95;
+
+#[derive(Default)]
+pub struct Solver {
+    clauses: Vec<Vec<Lit>>,
+    watches: Vec<Vec<usize>>, // Per literal, the clauses watching it
+    value: Vec<Option<bool>>, // Per variable
+    level: Vec<usize>,
+    reason: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    levels: Vec<usize>, // Where each decision level starts on the trail
+    head: usize,        // Next trail entry to propagate
+    activity: Vec<f64>,
+    bump: f64,
+    heap: Heap,
+    phase: Vec<bool>,
+    seen: Vec<bool>,
+    model: Vec<bool>,
+    unsat: bool,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self {
+            bump: 1.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_var(&mut self) -> usize {
+        let var = self.value.len();
+        self.value.push(None);
+        self.level.push(0);
+        self.reason.push(None);
+        self.activity.push(0.0);
+        self.phase.push(false);
+        self.seen.push(false);
+        self.watches.push(vec![]);
+        self.watches.push(vec![]);
+        self.heap.insert(var, &self.activity);
+        var
+    }
+
+    // Returns false once the clauses are known to be unsatisfiable
+    pub fn add_clause(&mut self, lits: &[Lit]) -> bool {
+        if self.unsat {
+            return false;
+        }
+        self.backtrack(0);
+
+        let mut clause = vec![];
+        for &l in lits {
+            match self.lit_value(l) {
+                Some(true) => return true,
+                Some(false) => (),
+                None if clause.contains(&!l) => return true,
+                None if !clause.contains(&l) => clause.push(l),
+                None => (),
+            }
+        }
+
+        match clause.len() {
+            0 => self.unsat = true,
+            1 => {
+                self.assign(clause[0], None);
+                self.unsat = self.propagate().is_some();
+            }
+            _ => {
+                self.attach(clause);
+            }
+        }
+
+        !self.unsat
+    }
+
+    // Whether the clauses can all be satisfied, or None if stop said to give up first. stop is
+    // asked every RESTART_BASE conflicts
+    pub fn solve(&mut self, stop: impl Fn() -> bool) -> Option<bool> {
+        if self.unsat {
+            return Some(false);
+        }
+        self.backtrack(0);
+
+        let mut conflicts = 0;
+        let mut total = 0;
+        let mut restarts = 0;
+        let mut limit = RESTART_BASE * luby(restarts);
+
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.levels.is_empty() {
+                    self.unsat = true;
+                    return Some(false);
+                }
+
+                conflicts += 1;
+                total += 1;
+                if total % RESTART_BASE == 0 && stop() {
+                    self.backtrack(0);
+                    return None;
+                }
+                let (learnt, level) = self.analyze(conflict);
+                self.backtrack(level);
+
+                if learnt.len() == 1 {
+                    self.assign(learnt[0], None);
+                } else {
+                    let asserting = learnt[0];
+                    let c = self.attach(learnt);
+                    self.assign(asserting, Some(c));
+                }
+
+                self.bump /= DECAY;
+            } else if conflicts >= limit {
+                conflicts = 0;
+                restarts += 1;
+                limit = RESTART_BASE * luby(restarts);
+                self.backtrack(0);
+            } else {
+                let Some(var) = self.pick() else {
+                    self.model = self.value.iter().map(|v| v.unwrap()).collect();
+                    return Some(true);
+                };
+
+                self.levels.push(self.trail.len());
+                self.assign(Lit::new(var, self.phase[var]), None);
+            }
+        }
+    }
+
+    // Value of a variable in the last model found
+    pub fn model(&self, var: usize) -> bool {
+        self.model[var]
+    }
+
+    fn lit_value(&self, l: Lit) -> Option<bool> {
+        self.value[l.var()].map(|v| v == l.positive())
+    }
+
+    fn attach(&mut self, clause: Vec<Lit>) -> usize {
+        let c = self.clauses.len();
+        self.watches[clause[0].index()].push(c);
+        self.watches[clause[1].index()].push(c);
+        self.clauses.push(clause);
+        c
+    }
+
+    fn assign(&mut self, l: Lit, reason: Option<usize>) {
+        let var = l.var();
+        self.value[var] = Some(l.positive());
+        self.level[var] = self.levels.len();
+        self.reason[var] = reason;
+        self.trail.push(l);
+    }
+
+    // Returns a conflicting clause, if there is one
+    fn propagate(&mut self) -> Option<usize> {
+        while self.head < self.trail.len() {
+            let falsified = !self.trail[self.head];
+            self.head += 1;
+
+            let mut watching = std::mem::take(&mut self.watches[falsified.index()]);
+            let mut kept = 0;
+
+            for i in 0..watching.len() {
+                let c = watching[i];
+
+                // Keep the falsified literal in position 1
+                if self.clauses[c][0] == falsified {
+                    self.clauses[c].swap(0, 1);
+                }
+
+                let first = self.clauses[c][0];
+                if self.lit_value(first) == Some(true) {
+                    watching[kept] = c;
+                    kept += 1;
+                    continue;
+                }
+
+                let replacement = (2..self.clauses[c].len())
+                    .find(|&k| self.lit_value(self.clauses[c][k]) != Some(false));
+
+                if let Some(k) = replacement {
+                    self.clauses[c].swap(1, k);
+                    let l = self.clauses[c][1];
+                    self.watches[l.index()].push(c);
+                    continue;
+                }
+
+                watching[kept] = c;
+                kept += 1;
+
+                if self.lit_value(first) == Some(false) {
+                    for j in i + 1..watching.len() {
+                        watching[kept] = watching[j];
+                        kept += 1;
+                    }
+                    watching.truncate(kept);
+                    self.watches[falsified.index()] = watching;
+                    return Some(c);
+                }
+
+                self.assign(first, Some(c));
+            }
+
+            watching.truncate(kept);
+            self.watches[falsified.index()] = watching;
+        }
+
+        None
+    }
+
+    // Learns a clause from a conflict, returning it (asserting literal first) and the level to
+    // go back to
+    fn analyze(&mut self, mut conflict: usize) -> (Vec<Lit>, usize) {
+        let current = self.levels.len();
+        let mut learnt = vec![Lit(0)];
+        let mut open = 0;
+        let mut index = self.trail.len();
+        let mut skip = 0;
+
+        loop {
+            for k in skip..self.clauses[conflict].len() {
+                let q = self.clauses[conflict][k];
+                let var = q.var();
+                if self.seen[var] || self.level[var] == 0 {
+                    continue;
+                }
+
+                self.seen[var] = true;
+                self.bump_activity(var);
+                if self.level[var] == current {
+                    open += 1;
+                } else {
+                    learnt.push(q);
+                }
+            }
+
+            // The next literal on this level involved in the conflict
+            loop {
+                index -= 1;
+                if self.seen[self.trail[index].var()] {
+                    break;
+                }
+            }
+
+            let p = self.trail[index];
+            self.seen[p.var()] = false;
+            open -= 1;
+
+            if open == 0 {
+                learnt[0] = !p;
+                break;
+            }
+
+            conflict = self.reason[p.var()].unwrap();
+            skip = 1;
+        }
+
+        for l in &learnt[1..] {
+            self.seen[l.var()] = false;
+        }
+
+        // Watch the literal from the highest remaining level second
+        let mut level = 0;
+        if let Some((k, _)) = learnt
+            .iter()
+            .enumerate()
+            .skip(1)
+            .max_by_key(|(_, l)| self.level[l.var()])
+        {
+            learnt.swap(1, k);
+            level = self.level[learnt[1].var()];
+        }
+
+        (learnt, level)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.levels.len() <= level {
+            return;
+        }
+
+        let start = self.levels[level];
+        for l in self.trail.drain(start..) {
+            let var = l.var();
+            self.value[var] = None;
+            self.reason[var] = None;
+            self.phase[var] = l.positive();
+            self.heap.insert(var, &self.activity);
+        }
+
+        self.levels.truncate(level);
+        self.head = self.head.min(start);
+    }
+
+    fn pick(&mut self) -> Option<usize> {
+        while let Some(var) = self.heap.pop(&self.activity) {
+            if self.value[var].is_none() {
+                return Some(var);
+            }
+        }
+        None
+    }
+
+    fn bump_activity(&mut self, var: usize) {
+        self.activity[var] += self.bump;
+
+        if self.activity[var] > 1e100 {
+            for a in &mut self.activity {
+                *a *= 1e-100;
+            }
+            self.bump *= 1e-100;
+        }
+
+        self.heap.increased(var, &self.activity);
+    }
+}
+
+// 1, 1, 2, 1, 1, 2, 4, 1, 1, 2, ...
+fn luby(mut i: usize) -> usize {
+    let mut size = 1;
+    let mut seq = 0;
+    while size < i + 1 {
+        seq += 1;
+        size = 2 * size + 1;
+    }
+
+    while size - 1 != i {
+        size = (size - 1) >> 1;
+        seq -= 1;
+        i %= size;
+    }
+
+    1 << seq
+}
+
+// Max-heap of variables by activity
+#[derive(Default)]
+struct Heap {
+    vars: Vec<usize>,
+    position: Vec<Option<usize>>,
+}
+
+impl Heap {
+    fn insert(&mut self, var: usize, activity: &[f64]) {
+        if self.position.len() <= var {
+            self.position.resize(var + 1, None);
+        }
+        if self.position[var].is_some() {
+            return;
+        }
+
+        self.position[var] = Some(self.vars.len());
+        self.vars.push(var);
+        self.up(self.vars.len() - 1, activity);
+    }
+
+    fn increased(&mut self, var: usize, activity: &[f64]) {
+        if let Some(i) = self.position[var] {
+            self.up(i, activity);
+        }
+    }
+
+    fn pop(&mut self, activity: &[f64]) -> Option<usize> {
+        let top = *self.vars.first()?;
+        let last = self.vars.pop().unwrap();
+        self.position[top] = None;
+
+        if last != top {
+            self.vars[0] = last;
+            self.position[last] = Some(0);
+            self.down(0, activity);
+        }
+
+        Some(top)
+    }
+
+    fn up(&mut self, mut i: usize, activity: &[f64]) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if activity[self.vars[parent]] >= activity[self.vars[i]] {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn down(&mut self, mut i: usize, activity: &[f64]) {
+        loop {
+            let mut largest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.vars.len()
+                    && activity[self.vars[child]] > activity[self.vars[largest]]
+                {
+                    largest = child;
+                }
+            }
+
+            if largest == i {
+                break;
+            }
+            self.swap(i, largest);
+            i = largest;
+        }
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.vars.swap(i, j);
+        self.position[self.vars[i]] = Some(i);
+        self.position[self.vars[j]] = Some(j);
+    }
+}
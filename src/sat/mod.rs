@@ -0,0 +1,345 @@
+mod cdcl;
+
+use cdcl::{Lit, Solver};
+
+use super::*;
+
+// An independent check on the rule engine: the board is encoded as CNF and handed to a SAT
+// solver, which finds a solution and then proves it's the only one by forbidding it and trying
+// again. Unlike solve, the verdict doesn't depend on how deep we're willing to guess. Carrying on
+// that way enumerates every solution.
+//
+// Each cell gets a land variable, and one ownership variable per island whose clue is close
+// enough to reach it. Islands are kept connected with distance variables ("this cell is within t
+// steps of the clue, through the island"), and their sizes with a sequential counter. The sea
+// has no bound on its shape like that, so instead of encoding its connectivity up front, each
+// solution with the sea in pieces gets a clause ruling out that particular cut, and we go again.
+
+pub enum Certificate {
+    Unsolvable,
+    Unique(Board),
+    NotUnique(Board, Board),
+}
+
+impl Certificate {
+    pub fn solution(&self) -> Option<&Board> {
+        match self {
+            Certificate::Unsolvable => None,
+            Certificate::Unique(b) | Certificate::NotUnique(b, _) => Some(b),
+        }
+    }
+
+    pub fn is_unique(&self) -> bool {
+        matches!(self, Certificate::Unique(_))
+    }
+
+    // Whether solve came to the same conclusion. If it didn't check uniqueness, finding a solution
+    // (the only one, if there is just one) is enough
+    pub fn agrees_with(&self, solution: &Solution) -> bool {
+        match self {
+            Certificate::Unsolvable => solution.contradiction,
+            Certificate::Unique(b) => {
+                solution.solved
+                    && solution.unique != Some(false)
+                    && same_tiles(b, solution.states.last().unwrap())
+            }
+            Certificate::NotUnique(..) => solution.solved && solution.unique != Some(true),
+        }
+    }
+}
+
+fn same_tiles(a: &Board, b: &Board) -> bool {
+    a.iter().zip(b.iter()).all(|((_, s), (_, t))| s == t)
+}
+
+pub fn certify(board: &Board) -> Certificate {
+    let mut solutions = solutions(board);
+
+    let Some(first) = solutions.next() else {
+        return Certificate::Unsolvable;
+    };
+
+    match solutions.next() {
+        None => Certificate::Unique(first),
+        Some(second) => Certificate::NotUnique(first, second),
+    }
+}
+
+// Every complete solution of the board, each one once, in no particular order
+pub fn solutions(board: &Board) -> Solutions {
+    Solutions {
+        encoding: Encoding::new(board),
+        budget: Budget::default(),
+    }
+}
+
+// Number of solutions, counting no further than limit
+pub fn count_solutions(board: &Board, limit: usize) -> usize {
+    solutions(board).take(limit).count()
+}
+
+pub struct Solutions {
+    encoding: Encoding,
+    budget: Budget,
+}
+
+impl Solutions {
+    // Give up once the config's time limit passes or it's cancelled. The step and guess limits
+    // don't mean anything here
+    pub fn within(mut self, config: &SolverConfig) -> Self {
+        self.budget = Budget::new(config);
+        self
+    }
+
+    // Why the search gave up, if it ran out before finding every solution
+    pub fn stopped(&self) -> Option<Stop> {
+        self.budget.stopped()
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        let solution = self.encoding.next(&self.budget)?;
+        self.encoding.block(&solution);
+        Some(solution)
+    }
+}
+
+struct Encoding {
+    solver: Solver,
+    board: Board,
+    land: Vec<Lit>, // Per cell
+}
+
+impl Encoding {
+    fn new(board: &Board) -> Self {
+        let (h, w) = board.dims();
+        let id = |(r, c): Coord| r * w + c;
+        let mut solver = Solver::new();
+
+        let truth = Lit::new(solver.new_var(), true);
+        solver.add_clause(&[truth]);
+
+        let land: Vec<Lit> = (0..h * w)
+            .map(|_| Lit::new(solver.new_var(), true))
+            .collect();
+
+        // Ownership variables, for islands which could reach each cell
+        let mut owners: Vec<Vec<(usize, Lit)>> = vec![vec![]; h * w];
+        for (k, is) in board.islands.iter().enumerate() {
+            for (c, _) in board.iter() {
+                let other_clue = board.lookup_island(c).is_some_and(|other| other != *is);
+                if dist(c, (is.r, is.c)) < is.n && !other_clue {
+                    owners[id(c)].push((k, Lit::new(solver.new_var(), true)));
+                }
+            }
+        }
+        let owner = |c: Coord, k: usize| {
+            owners[id(c)]
+                .iter()
+                .find(|&&(i, _)| i == k)
+                .map(|&(_, x)| x)
+        };
+
+        for (c, t) in board.iter() {
+            let l = land[id(c)];
+
+            // Land exactly when some island owns the cell, and never more than one
+            let mut any = vec![!l];
+            for (i, &(_, x)) in owners[id(c)].iter().enumerate() {
+                any.push(x);
+                solver.add_clause(&[!x, l]);
+                for &(_, y) in &owners[id(c)][i + 1..] {
+                    solver.add_clause(&[!x, !y]);
+                }
+            }
+            solver.add_clause(&any);
+
+            match t {
+                Water => solver.add_clause(&[!l]),
+                Land => solver.add_clause(&[l]),
+                Empty => true,
+            };
+
+            // Land next to an island is part of it
+            for n in neighbors(board, c) {
+                for &(k, x) in &owners[id(c)] {
+                    match owner(n, k) {
+                        Some(y) => solver.add_clause(&[!x, !land[id(n)], y]),
+                        None => solver.add_clause(&[!x, !land[id(n)]]),
+                    };
+                }
+            }
+        }
+
+        // No pools
+        for r in 0..h.saturating_sub(1) {
+            for c in 0..w.saturating_sub(1) {
+                let block = [(r, c), (r + 1, c), (r, c + 1), (r + 1, c + 1)];
+                solver.add_clause(&block.map(|c| land[id(c)]));
+            }
+        }
+
+        for (k, is) in board.islands.iter().enumerate() {
+            // The clue's own cell is part of its island, so a clue of 0 can't be satisfied
+            if is.n == 0 {
+                solver.add_clause(&[]);
+                continue;
+            }
+
+            let clue = (is.r, is.c);
+            solver.add_clause(&[owner(clue, k).unwrap()]);
+
+            let cells: Area = board
+                .iter()
+                .map(|(c, _)| c)
+                .filter(|&c| owner(c, k).is_some())
+                .collect();
+
+            // within[t] holds, per cell, whether it's at most t steps from the clue through the
+            // island. The clue itself always is
+            let mut within: Vec<Vec<Option<Lit>>> = vec![vec![None; h * w]; is.n];
+            for (t, layer) in within.iter_mut().enumerate() {
+                for &c in &cells {
+                    if c == clue {
+                        layer[id(c)] = Some(truth);
+                    } else if dist(c, clue) <= t {
+                        layer[id(c)] = Some(Lit::new(solver.new_var(), true));
+                    }
+                }
+            }
+
+            for &c in cells.iter().filter(|&&c| c != clue) {
+                let x = owner(c, k).unwrap();
+                solver.add_clause(&[!x, within[is.n - 1][id(c)].unwrap()]);
+
+                for t in dist(c, clue)..is.n {
+                    let d = within[t][id(c)].unwrap();
+                    solver.add_clause(&[!d, x]);
+
+                    let mut before = vec![!d];
+                    before.extend(within[t - 1][id(c)]);
+                    before.extend(
+                        neighbors(board, c)
+                            .into_iter()
+                            .filter_map(|n| within[t - 1][id(n)]),
+                    );
+                    solver.add_clause(&before);
+                }
+            }
+
+            let owned: Vec<Lit> = cells.iter().map(|&c| owner(c, k).unwrap()).collect();
+            exactly(&mut solver, truth, &owned, is.n);
+        }
+
+        Self {
+            solver,
+            board: board.clone(),
+            land,
+        }
+    }
+
+    // Another solution, if there is one and the budget lasts
+    fn next(&mut self, budget: &Budget) -> Option<Board> {
+        loop {
+            if budget.check().is_some() {
+                return None;
+            }
+            if self.solver.solve(|| budget.check().is_some()) != Some(true) {
+                return None;
+            }
+
+            let mut board = self.board.clone();
+            for (i, (c, _)) in self.board.iter().enumerate() {
+                board[c] = if self.solver.model(self.land[i].var()) {
+                    Land
+                } else {
+                    Water
+                };
+            }
+
+            let seas = seas(&board);
+            if seas.len() <= 1 {
+                return Some(board);
+            }
+
+            // A piece of sea, all water and walled in by land, can't be all the water there is
+            let (_, w) = board.dims();
+            let id = |(r, c): Coord| r * w + c;
+            for (i, sea) in seas.iter().enumerate() {
+                let elsewhere = seas[(i + 1) % seas.len()][0];
+
+                let mut cut: Vec<Lit> = sea.iter().map(|&c| self.land[id(c)]).collect();
+                cut.extend(
+                    surrounding(&board, sea)
+                        .into_iter()
+                        .map(|c| !self.land[id(c)]),
+                );
+                cut.push(self.land[id(elsewhere)]);
+
+                self.solver.add_clause(&cut);
+            }
+        }
+    }
+
+    fn block(&mut self, solution: &Board) {
+        let (_, w) = solution.dims();
+        let clause: Vec<Lit> = solution
+            .iter()
+            .map(|((r, c), t)| {
+                let l = self.land[r * w + c];
+                if t == Land { !l } else { l }
+            })
+            .collect();
+
+        self.solver.add_clause(&clause);
+    }
+}
+
+fn dist((r1, c1): Coord, (r2, c2): Coord) -> usize {
+    r1.abs_diff(r2) + c1.abs_diff(c2)
+}
+
+fn seas(board: &Board) -> Vec<Area> {
+    let (h, w) = board.dims();
+    let mut visited = vec![false; h * w];
+    let mut seas = vec![];
+
+    for (c, t) in board.iter() {
+        if t == Water && !visited[c.0 * w + c.1] {
+            let sea = area(board, c);
+            for &(r, c) in &sea {
+                visited[r * w + c] = true;
+            }
+            seas.push(sea);
+        }
+    }
+
+    seas
+}
+
+// Exactly n of xs hold, with a sequential counter: count[m] says at least m of the variables so far
+// hold
+fn exactly(solver: &mut Solver, truth: Lit, xs: &[Lit], n: usize) {
+    let mut count: Vec<Lit> = (0..=n + 1)
+        .map(|m| if m == 0 { truth } else { !truth })
+        .collect();
+
+    for &x in xs {
+        let mut next = vec![truth];
+        for m in 1..=n + 1 {
+            let s = Lit::new(solver.new_var(), true);
+            solver.add_clause(&[!count[m], s]);
+            solver.add_clause(&[!count[m - 1], !x, s]);
+            solver.add_clause(&[!s, count[m], count[m - 1]]);
+            solver.add_clause(&[!s, count[m], x]);
+            next.push(s);
+        }
+        count = next;
+    }
+
+    solver.add_clause(&[count[n]]);
+    solver.add_clause(&[!count[n + 1]]);
+}
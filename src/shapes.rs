@@ -0,0 +1,243 @@
+use std::ops::ControlFlow;
+
+use super::*;
+use Possibility::Isle;
+
+// Redelmeier-style enumeration of the shapes an island could take. Every connected set of cells
+// containing the clue is grown exactly once, by deciding for each cell on the frontier whether
+// it's in or out, so shapes can be counted or tested without allocating them.
+struct ShapeSearch<'a> {
+    known: &'a Knowledge,
+    board: Board,
+    island: Island,
+    required: usize, // Land cells that must belong to the island
+    found: usize,    // How many of them the shape contains
+    seen: Vec<bool>, // In the shape, on the frontier, or already ruled out
+    shape: Area,
+}
+
+impl ShapeSearch<'_> {
+    fn allowed(&self, c: Coord) -> bool {
+        self.known.get(c).contains(&Isle(self.island))
+    }
+
+    // Land already known to be part of the island
+    fn owned(&self, c: Coord) -> bool {
+        self.board[c] == Land && self.known.if_known(c) == Some(Isle(self.island))
+    }
+
+    fn id(&self, (r, c): Coord) -> usize {
+        r * self.board.dims().1 + c
+    }
+
+    fn land_at(&self, frontier: &Area) -> Option<usize> {
+        frontier.iter().rposition(|&c| self.board[c] == Land)
+    }
+
+    // Whether the shape, just completed by c, has no land left next to it
+    fn closed(&self, c: Coord, frontier: &Area) -> bool {
+        self.land_at(frontier).is_none()
+            && neighbors(&self.board, c)
+                .into_iter()
+                .all(|n| self.board[n] != Land || self.shape.contains(&n))
+    }
+
+    // A shape may not touch land belonging to another island
+    fn fits(&self, c: Coord) -> bool {
+        neighbors(&self.board, c)
+            .into_iter()
+            .all(|n| self.board[n] != Land || self.allowed(n))
+    }
+
+    fn grow(
+        &mut self,
+        mut frontier: Area,
+        f: &mut impl FnMut(&[Coord]) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        // Land next to the shape has to be part of it, so decide on that first
+        if let Some(i) = self.land_at(&frontier) {
+            let last = frontier.len() - 1;
+            frontier.swap(i, last);
+        }
+
+        while let Some(c) = frontier.pop() {
+            let land = self.board[c] == Land;
+
+            if self.fits(c) {
+                self.shape.push(c);
+                let owned = self.owned(c);
+                self.found += usize::from(owned);
+
+                if self.shape.len() == self.island.n {
+                    if self.found == self.required && self.closed(c, &frontier) {
+                        f(&self.shape)?;
+                    }
+                } else {
+                    let mut next = frontier.clone();
+                    let mut marked = vec![];
+
+                    for n in neighbors(&self.board, c) {
+                        let i = self.id(n);
+                        if !self.seen[i] && self.allowed(n) {
+                            self.seen[i] = true;
+                            marked.push(i);
+                            next.push(n);
+                        }
+                    }
+
+                    self.grow(next, f)?;
+
+                    for i in marked {
+                        self.seen[i] = false;
+                    }
+                }
+
+                self.shape.pop();
+                self.found -= usize::from(owned);
+            }
+
+            // Leaving out land next to the shape rules out the rest
+            if land {
+                break;
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+// Calls f with every shape the island could take, until it breaks
+pub fn visit_island_shapes(
+    known: &Knowledge,
+    island: Island,
+    mut f: impl FnMut(&[Coord]) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let board = known.board();
+    let (h, w) = board.dims();
+
+    let mut search = ShapeSearch {
+        known,
+        board,
+        island,
+        required: 0,
+        found: 0,
+        seen: vec![false; h * w],
+        shape: Vec::with_capacity(island.n),
+    };
+
+    search.required = search
+        .board
+        .iter()
+        .filter(|&(c, _)| search.owned(c))
+        .count();
+
+    let root = (island.r, island.c);
+    let i = search.id(root);
+    search.seen[i] = true;
+
+    search.grow(vec![root], &mut f)
+}
+
+// Island paths are the orders a shape can be grown in, the way the solver has always grown them:
+// from the clue, each cell comes after the last one (row-major) or next to it, and land is taken
+// as soon as it's the first cell in reach. A cell next to several cells of the path is reached
+// once through each of them, so the same path can turn up more than once. The rules were tuned
+// against these paths, repeats included, so they're kept rather than bare shapes.
+//
+// Calls f with each path of the shape (which has to be one the island could take) and the number
+// of times it turns up, in order
+pub fn visit_shape_paths(
+    known: &Knowledge,
+    island: Island,
+    shape: &[Coord],
+    f: &mut impl FnMut(&[Coord], usize),
+) {
+    fn grow(
+        known: &Knowledge,
+        island: Island,
+        shape: &[Coord],
+        path: &mut Area,
+        times: usize,
+        f: &mut impl FnMut(&[Coord], usize),
+    ) {
+        if path.len() == island.n {
+            return f(path, times);
+        }
+
+        let dims = known.dims();
+        let prev = *path.last().unwrap();
+        let mut reachable = vec![];
+        for &c in path.iter() {
+            for n in adjacent(dims, c) {
+                if !path.contains(&n)
+                    && (path.len() == 1 || n > prev || next_to(n, prev))
+                    && known.get(n).contains(&Isle(island))
+                {
+                    reachable.push(n);
+                }
+            }
+        }
+        reachable.sort();
+
+        let Some(&first) = reachable.first() else {
+            return;
+        };
+
+        if known.known_land(first) {
+            if shape.contains(&first) {
+                path.push(first);
+                grow(known, island, shape, path, times, f);
+                path.pop();
+            }
+            return;
+        }
+
+        for run in reachable.chunk_by(|a, b| a == b) {
+            if shape.contains(&run[0]) {
+                path.push(run[0]);
+                grow(known, island, shape, path, times * run.len(), f);
+                path.pop();
+            }
+        }
+    }
+
+    let mut path = Vec::with_capacity(island.n);
+    path.push((island.r, island.c));
+    grow(known, island, shape, &mut path, 1, f);
+}
+
+// Every path of the shape, repeats included, in order
+pub fn shape_paths(known: &Knowledge, island: Island, shape: &[Coord], out: &mut Vec<Area>) {
+    visit_shape_paths(known, island, shape, &mut |path, times| {
+        out.extend(std::iter::repeat_n(path.to_vec(), times));
+    });
+}
+
+// Number of paths the island could take, counting no further than limit
+pub fn count_island_paths(known: &Knowledge, island: Island, limit: usize) -> usize {
+    if limit == 0 {
+        return 0;
+    }
+
+    let mut count = 0;
+    let _ = visit_island_shapes(known, island, |shape| {
+        visit_shape_paths(known, island, shape, &mut |_, times| count += times);
+        if count < limit {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    });
+    count.min(limit)
+}
+
+// Every path the island could take, in order
+pub fn enumerate_island_paths(known: &Knowledge, island: Island) -> Vec<Area> {
+    let mut paths = vec![];
+    let _ = visit_island_shapes(known, island, |shape| {
+        shape_paths(known, island, shape, &mut paths);
+        ControlFlow::Continue(())
+    });
+    paths.sort();
+    paths
+}
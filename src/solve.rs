@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::{borrow::BorrowMut, sync::Arc, time::Instant};
 
 use ratatui::{
     buffer::Buffer,
@@ -12,13 +12,17 @@ use ratatui::{
 use super::*;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Solution {
-    pub unique: bool,
+    pub unique: Option<bool>, // None if it wasn't checked, see SolverConfig::check_unique
     pub contradiction: bool,
     pub states: Vec<Board>,
     pub reasons: Vec<Reason>,
     pub solved: bool,
     pub time: f32,
+    pub ambiguity: Option<Ambiguity>, // Where, if the solution is not unique
+    pub cost: usize,                  // Total Rule::cost of the rules behind each step
+    pub stopped: Option<Stop>,        // Why the solve gave up, if it ran out of budget
 }
 
 impl Solution {
@@ -45,73 +49,293 @@ impl Solution {
     }
 }
 
+// Two different solutions of the same puzzle, and what tells them apart
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ambiguity {
+    pub first: Board,
+    pub second: Board,
+    pub cells: Area,          // Where they differ
+    pub islands: Vec<Island>, // Which islands take a different shape
+}
+
+impl Ambiguity {
+    // Solutions found while focused on a region leave the rest of the board open, so that gets
+    // filled in from the final board
+    fn new((first, second): &(Board, Board), last: &Board) -> Self {
+        let complete = |board: &Board| {
+            let mut board = board.clone();
+            for (c, t) in last.iter() {
+                if board[c] == Empty {
+                    board[c] = t;
+                }
+            }
+            board
+        };
+        let (first, second) = (complete(first), complete(second));
+
+        let cells: Area = first
+            .iter()
+            .filter(|&(c, t)| t != second[c])
+            .map(|(c, _)| c)
+            .collect();
+
+        let mut islands: Vec<Island> = cells
+            .iter()
+            .flat_map(|&c| [&first, &second].map(|board| (board, c)))
+            .filter(|&(board, c)| board[c] == Land)
+            .filter_map(|(board, c)| {
+                area(board, c)
+                    .into_iter()
+                    .find_map(|c| board.lookup_island(c))
+            })
+            .collect();
+        islands.sort();
+        islands.dedup();
+
+        Self {
+            first,
+            second,
+            cells,
+            islands,
+        }
+    }
+
+    // The cells both solutions agree on
+    pub fn common(&self) -> Board {
+        let mut board = self.first.clone();
+        for &c in &self.cells {
+            board[c] = Empty;
+        }
+        board
+    }
+}
+
 pub fn solve(board: &Board) -> Solution {
-    let mut knowledge = Knowledge::new(board);
+    solve_with(board, &SolverConfig::default())
+}
+
+pub fn solve_with(board: &Board, config: &SolverConfig) -> Solution {
+    let mut knowledge = Knowledge::with_config(board, config);
 
     solve_knowing(&mut knowledge)
 }
 
 pub fn solve_with_limits(board: &Board, max_depth: usize) -> Solution {
-    let mut knowledge = Knowledge::new(board);
-
-    knowledge.depth_limit = 0;
-    knowledge.raise_max = Some(max_depth);
+    solve_with(board, &SolverConfig::new().max_depth(max_depth))
+}
 
-    solve_knowing(&mut knowledge)
+// Like solve, but tests hypotheses on several threads. The proof comes out the same
+pub fn solve_parallel(board: &Board, threads: usize) -> Solution {
+    solve_with(board, &SolverConfig::new().threads(threads))
 }
 
 pub fn solve_knowing(known: &mut Knowledge) -> Solution {
-    use ReasonKind::*;
-    
-    let board = known.board();
-    let mut states = vec![board.clone()];
+    let mut solver = Solver::knowing(known);
+
+    // Nobody looks at which cells each step changed, so they aren't worked out
+    let mut states = vec![solver.board().clone()];
     let mut reasons = vec![];
+    while let Some(step) = solver.next_step(false) {
+        states.push(step.board);
+        reasons.push(step.reason);
+    }
 
-    let start = Instant::now();
-    'solve: loop {
-        let board = known.board();
+    solver.solution(states, reasons)
+}
 
-        for rule in RULES {
-            rule(known, &board);
-            let reason = known.take_reason();
-
-            match reason {
-                MaxDepthReached => {
-                    if let Some(max) = known.raise_max && max <= known.depth_limit {
-                        break
-                    } else {
-                        known.raise_depth_limit();
-                        known.reason = Nil;
-                        continue 'solve;
-                    }
+// One deduction: why it was made, the cells whose tile it decided, and the board after it
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Step {
+    pub reason: Reason,
+    pub changed: Area,
+    pub board: Board,
+}
+
+// Solves one step at a time, as an iterator of the steps of the proof. It ends when the board is
+// solved or the solver can't go any further; knowledge() then says which
+pub struct Solver<K: BorrowMut<Knowledge> = Knowledge> {
+    known: K,
+    config: Arc<SolverConfig>,
+    board: Board, // After the last step
+    awake: Vec<bool>,
+    cost: usize,
+    start: Instant,
+    done: bool,
+}
+
+impl Solver {
+    pub fn new(board: &Board) -> Self {
+        Self::with_config(board, &SolverConfig::default())
+    }
+
+    pub fn with_config(board: &Board, config: &SolverConfig) -> Self {
+        Solver::knowing(Knowledge::with_config(board, config))
+    }
+}
+
+impl<K: BorrowMut<Knowledge>> Solver<K> {
+    // Carry on from what's already known
+    pub fn knowing(mut known: K) -> Self {
+        let config = known.borrow().config.clone();
+        let board = known.borrow().board();
+
+        // Rules which found nothing stay asleep until a change they care about happens. Rules are
+        // still tried in order, so the proof is the same as if we re-ran every rule each time.
+        let awake = vec![true; config.rules.len()];
+        known.borrow_mut().take_changes();
+
+        Self {
+            known,
+            config,
+            board,
+            awake,
+            cost: 0,
+            start: Instant::now(),
+            done: false,
+        }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn knowledge(&self) -> &Knowledge {
+        self.known.borrow()
+    }
+
+    // The next deduction, if there is one. Its changed cells are only filled in with diff (or for
+    // an observer)
+    fn advance(&mut self, diff: bool) -> Option<Step> {
+        use ReasonKind::*;
+
+        let known = self.known.borrow_mut();
+        let rules = &self.config.rules;
+
+        'solve: loop {
+            let board = known.snapshot();
+
+            for (i, &rule) in rules.iter().enumerate() {
+                if !self.awake[i] {
+                    continue;
                 }
-                Contradiction => break,
-                Loud(reason) => {
-                    states.push(known.board());
-                    reasons.push(reason);
-                    continue 'solve;
+
+                if known.out_of_budget() {
+                    return None;
                 }
-                Quiet(_) => {
-                    continue 'solve;
+
+                rule.apply(known, &board);
+                let reason = known.take_reason();
+
+                self.awake[i] = reason != Nil;
+                wake(&mut self.awake, rules, known.take_changes());
+
+                match reason {
+                    MaxDepthReached => {
+                        if let Some(max) = known.raise_max && max <= known.depth_limit {
+                            return None;
+                        } else {
+                            known.raise_depth_limit();
+                            known.reason = Nil;
+                            wake(&mut self.awake, rules, known.take_changes());
+                            continue 'solve;
+                        }
+                    }
+                    Contradiction => {
+                        known.observe(|o| o.contradiction(known.depth));
+                        return None;
+                    }
+                    Interrupted => return None,
+                    Loud(reason) => {
+                        let next = known.board();
+                        let changed: Area = if diff || known.config.observer.is_some() {
+                            next.iter()
+                                .filter(|&(c, t)| board[c] != t)
+                                .map(|(c, _)| c)
+                                .collect()
+                        } else {
+                            vec![]
+                        };
+                        known.observe(|o| o.step(rule, &reason, &changed, known.depth));
+
+                        self.cost += rule.cost();
+                        known.budget.step();
+
+                        let guessed =
+                            matches!(reason, Reason::ByContradiction(_) | Reason::Bifurcation);
+                        if self.config.stop_at_guess && guessed && known.depth == 0 {
+                            self.done = true;
+                        }
+
+                        self.board = next.clone();
+                        return Some(Step {
+                            reason,
+                            changed,
+                            board: next,
+                        });
+                    }
+                    Quiet(_) => {
+                        continue 'solve;
+                    }
+                    Nil => (),
                 }
-                Nil => (),
             }
+
+            return None;
         }
+    }
+
+    fn next_step(&mut self, diff: bool) -> Option<Step> {
+        if self.done {
+            return None;
+        }
+
+        let step = self.advance(diff);
+        self.done |= step.is_none();
+        step
+    }
+
+    // Sums up a finished solve, given the steps it took
+    fn solution(mut self, mut states: Vec<Board>, reasons: Vec<Reason>) -> Solution {
+        let known = self.known.borrow_mut();
 
         // Push final state, so we have completed board at the end
         let board = known.board();
         let solved = known.solved();
+
+        // Only the top level's is ever looked at
+        let ambiguity = match &known.witnesses {
+            Some(witnesses) if known.depth == 0 => Some(Ambiguity::new(witnesses, &board)),
+            _ => None,
+        };
         states.push(board);
 
-        let time = Instant::now().duration_since(start).as_secs_f32();
-        break Solution {
+        Solution {
             states,
             reasons,
             solved,
-            contradiction: known.reason == Contradiction,
-            time,
-            unique: known.unique,
-        };
+            contradiction: known.reason == ReasonKind::Contradiction,
+            time: self.start.elapsed().as_secs_f32(),
+            unique: (known.config.check_unique || !known.unique).then_some(known.unique),
+            ambiguity,
+            cost: self.cost,
+            stopped: known.budget.stopped(),
+        }
+    }
+}
+
+impl<K: BorrowMut<Knowledge>> Iterator for Solver<K> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        self.next_step(true)
+    }
+}
+
+fn wake(awake: &mut [bool], rules: &[&dyn Rule], changes: Changes) {
+    for (awake, rule) in awake.iter_mut().zip(rules) {
+        *awake |= rule.wakes().intersects(changes);
     }
 }
 
@@ -119,8 +343,10 @@ impl Widget for &Solution {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let title = Line::from("Solution Info".blue().bold());
 
-        let solved_line = if self.solved && !self.unique {
+        let solved_line = if self.solved && self.unique == Some(false) {
             "Solution found, but it is not unique".yellow().bold()
+        } else if self.solved && self.unique.is_none() {
+            "Solution found, but not checked for uniqueness".light_yellow().bold()
         } else if self.solved
             && !self
                 .reasons
@@ -132,6 +358,8 @@ impl Widget for &Solution {
             "Solved with guesses".light_yellow().bold()
         } else if self.contradiction {
             "Board is unsolvable".red().bold()
+        } else if let Some(stop) = self.stopped {
+            format!("Gave up: {}", stop).red().bold()
         } else {
             // This should happen only if the puzzle has zero solutions
             "Max search depth reached".red().bold()
@@ -149,12 +377,30 @@ impl Widget for &Solution {
 
         let info = Line::from(solved_line);
 
+        let mut lines = vec![info, length_line, time_line];
+
+        if let Some(ambiguity) = &self.ambiguity {
+            let islands: Vec<String> = ambiguity
+                .islands
+                .iter()
+                .map(|is| format!("{} at ({}, {})", is.n, is.r, is.c))
+                .collect();
+
+            lines.push(Line::from(vec![
+                "Differs in: ".into(),
+                format!("{} cells", ambiguity.cells.len()).bold().blue(),
+                ", islands ".into(),
+                islands.join(", ").bold().blue(),
+                " (a to compare)".into(),
+            ]));
+        }
+
         let block = Block::bordered()
             .title(title.centered())
             .padding(Padding::horizontal(1))
             .border_set(border::ROUNDED);
 
-        Paragraph::new(vec![info, length_line, time_line])
+        Paragraph::new(lines)
             .block(block)
             .render(area, buf)
     }
@@ -16,18 +16,32 @@ use ratatui::{
 
 use super::*;
 
+// Solutions counted past this are just shown as "more than"
+const COUNT_LIMIT: usize = 100;
+
+// So a board which is too hard doesn't freeze the screen
+const TIME_LIMIT: Duration = Duration::from_secs(10);
+
 pub struct EntryScreen {
     board: Board,
     cursor: Coord,
     cursor_clock: Instant,
+    counted: Option<(Board, usize, Option<Stop>)>, // Board last counted, how many, and why it gave up
+    config: SolverConfig,
 }
 
 impl EntryScreen {
     pub fn new() -> Self {
+        Self::with_config(SolverConfig::new().time_limit(TIME_LIMIT))
+    }
+
+    pub fn with_config(config: SolverConfig) -> Self {
         Self {
             board: Board::empty(8, 6),
             cursor: (2, 2),
             cursor_clock: Instant::now(),
+            counted: None,
+            config,
         }
     }
 
@@ -133,10 +147,16 @@ impl EntryScreen {
                     }
                 }
             }
+            Char('c') => {
+                // Under the same time limit as solving
+                let mut solutions = solutions(&self.board).within(&self.config);
+                let count = solutions.by_ref().take(COUNT_LIMIT + 1).count();
+                self.counted = Some((self.board.clone(), count, solutions.stopped()));
+            }
             // Jeez. This menu thing is getting a bit unwieldy...
             // If it ain't broke...
             Enter => {
-                return Ok(Some(UI::solver(self.board.clone())));
+                return Ok(Some(UI::solver_with(self.board.clone(), &self.config)));
             }
             _ => (),
         }
@@ -199,15 +219,33 @@ impl Widget for &EntryScreen {
             " 0-9/<Backspace> ".bold().blue(),
             " Resize:".into(),
             " -=_+ ".bold().blue(),
+            " Count Solutions:".into(),
+            " c ".bold().blue(),
         ]);
 
-        let size_info = Line::from(vec![
+        let mut size_info = Line::from(vec![
             " Height: ".into(),
             format!("{} ", h).red().bold(),
             " Width: ".into(),
             format!("{} ", w).red().bold(),
         ]);
 
+        // Only while the board is still the one that was counted
+        if let Some((board, count, stopped)) = &self.counted
+            && *board == self.board
+        {
+            let count = if let Some(stop) = stopped {
+                format!("{}+, gave up ({}) ", count, stop)
+            } else if *count > COUNT_LIMIT {
+                format!("{}+ ", COUNT_LIMIT)
+            } else {
+                format!("{} ", count)
+            };
+
+            size_info.push_span(" Solutions: ");
+            size_info.push_span(count.red().bold());
+        }
+
         let block = Block::bordered()
             .title(title.centered())
             .border_set(border::ROUNDED);
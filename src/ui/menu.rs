@@ -21,6 +21,15 @@ impl UI {
         Self::Solver(SolverScreen::new(board))
     }
 
+    // Screens which solve with config instead of the default
+    pub fn entry_with(config: SolverConfig) -> Self {
+        Self::Entry(EntryScreen::with_config(config))
+    }
+
+    pub fn solver_with(board: Board, config: &SolverConfig) -> Self {
+        Self::Solver(SolverScreen::with_config(board, config))
+    }
+
     pub fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
         use UI::*;
         loop {
@@ -2,6 +2,7 @@ pub mod board;
 pub mod entry_screen;
 pub mod menu;
 pub mod solver_screen;
+pub mod svg;
 pub mod utils;
 
 use super::*;
@@ -18,14 +18,20 @@ pub struct SolverScreen {
     solution: Solution,
     list_state: ListState,
     alive: bool,
+    comparing: bool, // Showing both solutions of an ambiguous puzzle
 }
 
 impl SolverScreen {
     pub fn new(board: Board) -> Self {
+        Self::with_config(board, &SolverConfig::default())
+    }
+
+    pub fn with_config(board: Board, config: &SolverConfig) -> Self {
         Self {
-            solution: solve(&board),
+            solution: solve_with(&board, config),
             list_state: ListState::default().with_selected(Some(0)),
             alive: true,
+            comparing: false,
         }
     }
 
@@ -62,6 +68,9 @@ impl SolverScreen {
             Down | Char('j') => {
                 self.list_state.select_next();
             }
+            Char('a') if self.solution.ambiguity.is_some() => {
+                self.comparing = !self.comparing;
+            }
             _ => (),
         }
 
@@ -104,6 +113,19 @@ impl SolverScreen {
 
         frame.render_stateful_widget(proof_step, proof_area, &mut self.list_state);
 
+        // Both solutions, with the cells where they differ highlighted
+        if self.comparing
+            && let Some(ambiguity) = &self.solution.ambiguity
+        {
+            let common = ambiguity.common();
+            let [first_area, second_area] =
+                Layout::vertical([Fill(1), Fill(1)]).areas(board_area);
+
+            frame.render_widget(Diff(&ambiguity.first, &common), first_area);
+            frame.render_widget(Diff(&ambiguity.second, &common), second_area);
+            return;
+        }
+
         let selected = self.list_state.selected().unwrap();
         let board = &self.solution.states[selected];
 
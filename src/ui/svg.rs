@@ -0,0 +1,138 @@
+use std::{fmt::Write, fs, io, path::Path};
+
+use super::board::Diff;
+use super::*;
+
+const CELL: usize = 40;
+const MARGIN: usize = 8;
+const CAPTION: usize = 28;
+
+const EMPTY: &str = "#ffffff";
+const WATER: &str = "#2f4f6f";
+const LAND: &str = "#f2f7ee";
+const NEW_WATER: &str = "#4fb3d9";
+const NEW_LAND: &str = "#9fe09f";
+const GRID: &str = "#9a9a9a";
+
+impl Board {
+    pub fn svg(&self) -> String {
+        Diff(self, self).svg()
+    }
+}
+
+impl Diff<'_> {
+    // Highlights cells which were Empty in the previous board, like the widget does
+    pub fn svg(&self) -> String {
+        render(self.0, self.1, None)
+    }
+}
+
+impl Solution {
+    // Writes one numbered SVG per state, captioned with the reason that led to it
+    pub fn write_svgs(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let last = self.states.len() - 1;
+        for (i, board) in self.states.iter().enumerate() {
+            let (prev, caption) = if i == 0 {
+                (board, "Initial Board".to_string())
+            } else if i == last {
+                (board, "Final Board".to_string())
+            } else {
+                (&self.states[i - 1], format!("{}: {}", i, self.reasons[i - 1]))
+            };
+
+            let svg = render(board, prev, Some(&caption));
+            fs::write(dir.join(format!("step_{:03}.svg", i)), svg)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn render(board: &Board, prev: &Board, caption: Option<&str>) -> String {
+    let (h, w) = board.dims();
+    let width = w * CELL + 2 * MARGIN;
+    let grid_height = h * CELL + 2 * MARGIN;
+    let height = grid_height + if caption.is_some() { CAPTION } else { 0 };
+
+    let mut out = String::new();
+
+    // Writing to a String can't fail
+    let mut line = |s: String| {
+        let _ = writeln!(out, "{}", s);
+    };
+
+    line(format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    ));
+    line(format!(
+        r#"<rect width="{width}" height="{height}" fill="{EMPTY}"/>"#
+    ));
+
+    for ((r, c), tile) in board.iter() {
+        let fill = match (tile, prev[(r, c)]) {
+            (Water, Empty) => NEW_WATER,
+            (Land, Empty) => NEW_LAND,
+            (Water, _) => WATER,
+            (Land, _) => LAND,
+            (Empty, _) => EMPTY,
+        };
+
+        let (x, y) = (MARGIN + c * CELL, MARGIN + r * CELL);
+        line(format!(
+            r#"<rect x="{x}" y="{y}" width="{CELL}" height="{CELL}" fill="{fill}"/>"#
+        ));
+    }
+
+    for r in 0..=h {
+        let y = MARGIN + r * CELL;
+        let x2 = MARGIN + w * CELL;
+        line(format!(
+            r#"<line x1="{MARGIN}" y1="{y}" x2="{x2}" y2="{y}" stroke="{GRID}" stroke-width="1"/>"#
+        ));
+    }
+
+    for c in 0..=w {
+        let x = MARGIN + c * CELL;
+        let y2 = MARGIN + h * CELL;
+        line(format!(
+            r#"<line x1="{x}" y1="{MARGIN}" x2="{x}" y2="{y2}" stroke="{GRID}" stroke-width="1"/>"#
+        ));
+    }
+
+    line(format!(
+        r#"<rect x="{MARGIN}" y="{MARGIN}" width="{}" height="{}" fill="none" stroke="black" stroke-width="3"/>"#,
+        w * CELL,
+        h * CELL
+    ));
+
+    for &Island { r, c, n } in &board.islands {
+        let x = MARGIN + c * CELL + CELL / 2;
+        let y = MARGIN + r * CELL + CELL / 2;
+        let size = if n < 10 { CELL * 3 / 5 } else { CELL / 2 };
+        line(format!(
+            r#"<text x="{x}" y="{y}" font-family="sans-serif" font-size="{size}" text-anchor="middle" dominant-baseline="central">{n}</text>"#
+        ));
+    }
+
+    if let Some(caption) = caption {
+        let x = width / 2;
+        let y = grid_height + CAPTION / 2;
+        line(format!(
+            r#"<text x="{x}" y="{y}" font-family="sans-serif" font-size="16" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            escape(caption)
+        ));
+    }
+
+    line("</svg>".to_string());
+
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
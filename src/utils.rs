@@ -1,5 +1,3 @@
-use std::iter::once;
-
 use super::*;
 
 pub type Area = Vec<Coord>;
@@ -10,6 +8,23 @@ pub fn all_neighbors(board: &Board, c: Coord) -> Area {
     all
 }
 
+// Same as neighbors, given just the board's dimensions
+pub fn adjacent((h, w): Coord, (r, c): Coord) -> impl Iterator<Item = Coord> {
+    [
+        (r > 0).then(|| (r - 1, c)),
+        (r + 1 < h).then_some((r + 1, c)),
+        (c > 0).then(|| (r, c - 1)),
+        (c + 1 < w).then_some((r, c + 1)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+// Whether the cells share a side
+pub fn next_to((r, c): Coord, (r2, c2): Coord) -> bool {
+    r.abs_diff(r2) + c.abs_diff(c2) == 1
+}
+
 pub fn neighbors(board: &Board, (r, c): Coord) -> Area {
     let mut neighbors = vec![];
 
@@ -87,10 +102,17 @@ pub fn area(board: &Board, (r, c): (usize, usize)) -> Area {
 }
 
 pub fn surrounding(board: &Board, area: &Area) -> Area {
+    // Linear search is faster for the small areas that island paths are built from
+    let inside: Option<Set<Coord>> = (area.len() > 16).then(|| area.iter().copied().collect());
+    let contains = |n: &Coord| match &inside {
+        Some(inside) => inside.contains(n),
+        None => area.contains(n),
+    };
+
     let mut all_neighbors = vec![];
     for &(r, c) in area {
         for n in neighbors(board, (r, c)) {
-            if !area.contains(&n) {
+            if !contains(&n) {
                 all_neighbors.push(n);
             }
         }
@@ -99,66 +121,6 @@ pub fn surrounding(board: &Board, area: &Area) -> Area {
     all_neighbors
 }
 
-pub fn enumerate_island_paths(
-    note: &Knowledge,
-    island: Island,
-) -> impl Iterator<Item = Vec<Coord>> {
-    use Possibility::*;
-
-    fn dfs<'a>(
-        note: &'a Knowledge,
-        island: Island,
-        mut current: Vec<Coord>,
-        board: Board,
-    ) -> Box<dyn Iterator<Item = Vec<Coord>> + 'a> {
-        if current.len() == island.n {
-            return Box::new(once(current.clone()));
-        }
-
-        let mut reachable = surrounding(&board, &current);
-
-        // At first step, no lexicographic ordering required
-        if current.len() != 1 {
-            let prev = current.last().unwrap();
-            let news = neighbors(&board, *prev);
-            reachable.retain(|t| t > prev || news.contains(t));
-        }
-
-        reachable.retain(|&c| note.get(c).contains(&Isle(island)));
-        reachable.sort();
-
-        if reachable.is_empty() {
-            return Box::new([].into_iter());
-        }
-
-        let f = reachable[0];
-
-        if board[f] == Land {
-            current.push(f);
-            return dfs(note, island, current, board);
-        }
-
-        Box::new(reachable.clone().into_iter().flat_map(move |n| {
-            let mut new = current.clone();
-            new.push(n);
-
-            dfs(note, island, new, board.clone())
-        }))
-    }
-
-    let board = note.board();
-
-    let ic = (island.r, island.c);
-    dfs(note, island, vec![ic], board.clone()).filter(move |path| {
-        let surrounding = surrounding(&board, path);
-
-        !board
-            .iter()
-            .any(|(c, t)| t == Land && note.if_known(c) == Some(Isle(island)) && !path.contains(&c))
-            && !surrounding.iter().any(|&c| board[c] == Land)
-    })
-}
-
 pub fn noncontiguous_board(board: &Board) -> bool {
     let count = board.iter().filter(|&(_, t)| t == Water).count();
 
@@ -202,7 +164,7 @@ pub fn flood_count(board: &Board, start: Coord) -> usize {
     count
 }
 
-pub fn board_with(board: &Board, path: &Area) -> Board {
+pub fn board_with(board: &Board, path: &[Coord]) -> Board {
     let mut board = board.clone();
 
     for &c in path {
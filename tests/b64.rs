@@ -0,0 +1,35 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use nurikabe::*;
+
+// A v1 encoding of a board with no clues or tiles
+fn header(h: &[u8], w: &[u8]) -> String {
+    let mut bytes = vec![0, 1];
+    bytes.extend(h);
+    bytes.extend(w);
+    bytes.extend([0, 0, 0]);
+    STANDARD.encode(bytes)
+}
+
+#[test]
+fn rejects_bad_dimensions() {
+    // 2^40 each, as varints
+    let huge = [0x80, 0x80, 0x80, 0x80, 0x80, 0x20];
+    assert_eq!(
+        Board::from_b64(&header(&huge, &huge)).err(),
+        Some(B64Error::BadDimensions(1 << 40, 1 << 40))
+    );
+
+    assert_eq!(
+        Board::from_b64(&header(&[3], &[0])).err(),
+        Some(B64Error::BadDimensions(3, 0))
+    );
+}
+
+#[test]
+fn rejects_zero_clues() {
+    let board = Board::from_islands(3, 3, [Island { r: 1, c: 2, n: 0 }].into_iter());
+    assert_eq!(
+        Board::from_b64(&board.b64()).err(),
+        Some(B64Error::ZeroClue((1, 2)))
+    );
+}
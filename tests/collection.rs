@@ -0,0 +1,78 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use nurikabe::*;
+
+fn board() -> Board {
+    let mut board = Board::from_islands(2, 3, [Island { r: 0, c: 0, n: 2 }].into_iter());
+    board.tiles[5] = Tile::Water;
+    board
+}
+
+#[test]
+fn round_trips_metadata() {
+    let mut puzzle = Puzzle::new(board());
+    puzzle.id = Some("a%b".to_string());
+    puzzle.source = Some("tab\there".to_string());
+    puzzle.settings = Some("two\r\nlines".to_string());
+    puzzle.difficulty = Some(12);
+    puzzle.time = Some(0.5);
+    puzzle.solution = Some(board());
+
+    let line = puzzle.to_string();
+    assert!(line.contains("\tid=a%25b\t"));
+    assert!(line.contains("\tsource=tab%09here\t"));
+    assert!(line.contains("\tsettings=two%0D%0Alines\t"));
+
+    let back: Puzzle = line.parse().unwrap();
+    assert_eq!(back.board, puzzle.board);
+    assert_eq!(back.id, puzzle.id);
+    assert_eq!(back.source, puzzle.source);
+    assert_eq!(back.settings, puzzle.settings);
+    assert_eq!(back.difficulty, Some(12));
+    assert_eq!(back.time, Some(0.5));
+    assert_eq!(back.solution, puzzle.solution);
+}
+
+#[test]
+fn loads_legacy_b64l() {
+    // The unversioned layout: byte dimensions, u16 counts, then byte coordinates
+    let legacy = STANDARD.encode([2, 3, 0, 1, 0, 0, 0, 1, 0, 0, 2, 1, 2]);
+    let text = format!("{}\n\n{}\n", legacy, board().b64());
+
+    let collection: PuzzleCollection = text.parse().unwrap();
+    assert_eq!(collection.puzzles.len(), 2);
+    for puzzle in collection.iter() {
+        assert_eq!(puzzle.board, board());
+        assert_eq!(puzzle.id, None);
+        assert_eq!(puzzle.difficulty, None);
+    }
+}
+
+#[test]
+fn errors_give_the_line() {
+    let good = board().b64();
+    let line_error = |text: String| match text.parse::<PuzzleCollection>() {
+        Err(CollectionError::Line { line, kind }) => (line, kind),
+        other => panic!("expected a line error, got {:?}", other),
+    };
+
+    assert_eq!(
+        line_error(format!("{}\n\n{}\tid=%2", good, good)),
+        (3, LineError::BadEscape)
+    );
+    assert_eq!(
+        line_error(format!("{}\n{}\tcolour=red", good, good)),
+        (2, LineError::UnknownField("colour".to_string()))
+    );
+    assert_eq!(
+        line_error(format!("{}\tdifficulty=hard", good)),
+        (1, LineError::BadValue("difficulty".to_string()))
+    );
+    assert_eq!(
+        line_error(format!("{}\tid", good)),
+        (1, LineError::MissingValue("id".to_string()))
+    );
+    assert!(matches!(
+        line_error(format!("{}\n!!!", good)),
+        (2, LineError::BadBoard(_))
+    ));
+}
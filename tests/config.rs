@@ -0,0 +1,48 @@
+use nurikabe::*;
+
+#[test]
+fn unknown_rule_names_are_errors() {
+    let unknown = |name: &str| Some(UnknownRule(name.to_string()));
+    assert_eq!(SolverConfig::new().rules(&["nope"]).err(), unknown("nope"));
+    assert_eq!(SolverConfig::new().without("nope").err(), unknown("nope"));
+
+    // Only rules still in the config can be removed
+    let first = RULES[0].name();
+    let config = SolverConfig::new().without(first).unwrap();
+    assert_eq!(config.rules.len(), RULES.len() - 1);
+    assert_eq!(config.without(first).err(), unknown(first));
+}
+
+#[test]
+fn display_lists_the_enabled_rules() {
+    let config = SolverConfig::new().rules(&["pools", "guess"]).unwrap();
+    let listing = config.to_string();
+    let lines: Vec<_> = listing.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(" 1. pools (1): "));
+    assert!(lines[1].starts_with(" 2. guess (10): "));
+}
+
+#[test]
+fn unchecked_uniqueness_is_reported_as_such() {
+    let config = SolverConfig::new().check_unique(false);
+
+    let board = Board::from_islands(2, 2, [Island { r: 0, c: 0, n: 3 }].into_iter());
+    let solution = solve_with(&board, &config);
+    assert!(solution.solved);
+    assert_eq!(solution.unique, None);
+    assert!(solution.ambiguity.is_none());
+    assert!(certify(&board).agrees_with(&solution));
+
+    let board: Board = "...2
+                        ....
+                        ....
+                        .4.."
+        .parse()
+        .unwrap();
+    let solution = solve_with(&board, &config);
+    assert!(solution.solved);
+    assert_eq!(solution.unique, None);
+    assert!(certify(&board).agrees_with(&solution));
+    assert_eq!(solve(&board).unique, Some(true));
+}
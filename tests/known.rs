@@ -0,0 +1,45 @@
+use nurikabe::*;
+
+type Snapshot = (Vec<Vec<u64>>, Vec<Vec<Area>>);
+
+// Every cell's possibilities, and every island's paths
+fn snapshot(known: &mut Knowledge) -> Snapshot {
+    let possibilities = known.possibilities().map(|p| p.words().to_vec()).collect();
+    let islands = known.island_set().clone();
+    let paths = islands
+        .into_iter()
+        .map(|island| known.island_paths(island).clone())
+        .collect();
+    (possibilities, paths)
+}
+
+#[test]
+fn bifurcate_restores_possibilities_and_paths() {
+    let board = Board::from_islands(
+        6,
+        6,
+        [(0, 0, 3), (1, 4, 4), (3, 2, 2), (5, 0, 1), (5, 5, 3)]
+            .into_iter()
+            .map(Island::from),
+    );
+    let mut known = Knowledge::new(&board);
+    let before = snapshot(&mut known);
+
+    for (c, _) in board.iter() {
+        if known.tile_known(c).is_some() {
+            continue;
+        }
+
+        for land in [true, false] {
+            known.bifurcate(|known| {
+                if land {
+                    known.set_land(Reason::Bifurcation, c);
+                } else {
+                    known.set_sea(Reason::Bifurcation, c);
+                }
+                solve_knowing(known);
+            });
+            assert!(snapshot(&mut known) == before, "{:?} as land: {}", c, land);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::sync::{Arc, Mutex};
+
+use nurikabe::*;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Step(Reason, usize),
+    Started(usize),
+    Ended(usize),
+}
+
+// Writes down what it's told, in order
+#[derive(Default)]
+struct Recorder(Mutex<Vec<Event>>);
+
+impl Recorder {
+    fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+impl SolverObserver for Recorder {
+    fn step(&self, _rule: &dyn Rule, reason: &Reason, _changed: &[Coord], depth: usize) {
+        self.0.lock().unwrap().push(Event::Step(reason.clone(), depth));
+    }
+
+    fn bifurcation_started(&self, depth: usize) {
+        self.0.lock().unwrap().push(Event::Started(depth));
+    }
+
+    fn bifurcation_ended(&self, depth: usize) {
+        self.0.lock().unwrap().push(Event::Ended(depth));
+    }
+}
+
+fn board() -> Board {
+    ".5...
+     ...5.
+     ....."
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn top_level_steps_are_the_reasons() {
+    let recorder = Arc::new(Recorder::default());
+    let config = SolverConfig::new()
+        .max_depth(2)
+        .observe_with(recorder.clone());
+    let solution = solve_with(&board(), &config);
+    assert!(solution.solved);
+
+    let events = recorder.take();
+    let top: Vec<Reason> = events
+        .iter()
+        .filter_map(|e| match e {
+            Event::Step(reason, 0) => Some(reason.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(top, solution.reasons);
+
+    // Steps inside branches are reported too, between their branch's start and end
+    let mut depth = 0;
+    for event in &events {
+        match *event {
+            Event::Started(d) => {
+                assert_eq!(d, depth + 1);
+                depth = d;
+            }
+            Event::Ended(d) => {
+                assert_eq!(d, depth);
+                depth -= 1;
+            }
+            Event::Step(_, d) => assert_eq!(d, depth),
+        }
+    }
+    assert_eq!(depth, 0);
+    assert!(events.iter().any(|e| matches!(e, Event::Step(_, 1))));
+}
+
+#[test]
+fn repeated_hypotheses_come_from_the_cache() {
+    let recorder = Arc::new(Recorder::default());
+    let config = SolverConfig::new().observe_with(recorder.clone());
+    let mut known = Knowledge::with_config(&board(), &config);
+    let hypothesis = Hypothesis::Land((0, 3));
+
+    let first = known.explore(&hypothesis).unwrap();
+    let events = recorder.take();
+    assert!(events.iter().any(|e| matches!(e, Event::Step(_, 1))));
+
+    // Nothing is solved the second time, but the branch is still reported
+    let second = known.explore(&hypothesis).unwrap();
+    assert_eq!(recorder.take(), [Event::Started(1), Event::Ended(1)]);
+    assert_eq!(second.reason, first.reason);
+    assert_eq!(second.solution, first.solution);
+    assert_eq!(second.steps, first.steps);
+}
@@ -0,0 +1,167 @@
+use nurikabe::*;
+
+// The proofs solve has always given for these boards. Faster ways of finding island paths mustn't
+// change them
+
+fn proof(h: usize, w: usize, islands: &[(usize, usize, usize)]) -> (Solution, Vec<String>) {
+    let board = Board::from_islands(h, w, islands.iter().copied().map(Island::from));
+    let solution = solve(&board);
+    let reasons = solution.reasons.iter().map(|r| r.to_string()).collect();
+    (solution, reasons)
+}
+
+fn tiles(board: &Board) -> Vec<String> {
+    board
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|&t| match t {
+                    Tile::Land => 'o',
+                    Tile::Water => '#',
+                    Tile::Empty => '.',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn non_unique_proof() {
+    let (solution, reasons) = proof(2, 2, &[(0, 0, 3)]);
+
+    assert!(solution.solved);
+    assert_eq!(solution.unique, Some(false));
+    assert_eq!(reasons, ["Arbitrary", "Island completed"]);
+    assert_eq!(tiles(solution.states.last().unwrap()), ["oo", "#o"]);
+}
+
+#[test]
+fn very_hard_proof() {
+    let (solution, reasons) = proof(
+        14,
+        10,
+        &[
+            (0, 2, 7),
+            (1, 5, 7),
+            (4, 5, 2),
+            (4, 7, 5),
+            (5, 4, 4),
+            (5, 9, 6),
+            (6, 6, 8),
+            (7, 4, 7),
+            (10, 3, 8),
+            (11, 0, 2),
+            (11, 4, 2),
+            (11, 9, 3),
+            (12, 1, 3),
+            (13, 4, 4),
+        ],
+    );
+
+    assert!(solution.solved);
+    assert_eq!(solution.unique, Some(true));
+    assert_eq!(
+        reasons,
+        [
+            "Borders separate islands",
+            "Only one way to go",
+            "Island completed",
+            "Only one way to go",
+            "Only one way to go",
+            "Only one way to go",
+            "Only one way to go",
+            "Only one way to go",
+            "Island completed",
+            "Only one way to go",
+            "Only one way to go",
+            "Island completed",
+            "L-Corner",
+            "Connects edges",
+            "Wall pattern",
+            "Unreachable square",
+            "Only one way to go",
+            "Contradiction in 7 steps",
+            "Contradiction in 16 steps",
+            "Contradiction in 11 steps",
+            "Contradiction in 14 steps",
+            "Contradiction in 4 steps",
+            "Island must pass square",
+            "Borders separate islands",
+            "Contradiction in 5 steps",
+            "Contradiction in 5 steps",
+            "Contradiction in 4 steps",
+            "Island must pass square",
+            "Wall pattern",
+            "Only one way to go",
+            "Only one way to go",
+            "Borders separate islands",
+            "Only one way to go",
+            "Island completed",
+            "Only one way to go",
+            "Island must pass square",
+            "Island must pass square",
+            "Borders separate islands",
+            "Only one way to go",
+            "Borders separate islands",
+            "Only one way to go",
+            "Island must pass square",
+            "Borders separate islands",
+            "Island must pass square",
+            "Borders separate islands",
+            "Unreachable square",
+            "Island must pass square",
+            "Contradiction in 4 steps",
+            "Island completed",
+            "L-Corner",
+            "Contradiction in 11 steps",
+            "Island completed",
+            "Connects edges",
+            "Island must pass square",
+            "Island must pass square",
+            "Contradiction in 1 steps",
+            "Island completed",
+            "Contradiction in 1 steps",
+            "Island completed",
+            "L-Corner",
+            "Only one way to go",
+            "Only one way to go",
+            "Only one way to go",
+            "L-Corner",
+            "Only one way to go",
+            "Island completed",
+            "Contradiction in 4 steps",
+            "Borders separate islands",
+            "Island must border square",
+            "Island must pass square",
+            "Contradiction in 4 steps",
+            "Unreachable square",
+            "Contradiction in 1 steps",
+            "Island completed",
+            "Only one way to go",
+            "Contradiction in 1 steps",
+            "Island completed",
+            "L-Corner",
+            "Contradiction in 1 steps",
+            "Sea complete",
+        ]
+    );
+    assert_eq!(
+        tiles(solution.states.last().unwrap()),
+        [
+            "ooo#oo#o#o",
+            "o###oo#o#o",
+            "o#ooo##o#o",
+            "o####o#o#o",
+            "o#oo#o#o#o",
+            "###oo####o",
+            "#o####oo##",
+            "#oooo#oooo",
+            "#o#######o",
+            "#o#ooooo#o",
+            "o##o###o##",
+            "o#o#oo#o#o",
+            "#oo######o",
+            "####oooo#o",
+        ]
+    );
+}
@@ -0,0 +1,64 @@
+use nurikabe::*;
+
+fn island(r: usize, c: usize, n: usize) -> Island {
+    Island { r, c, n }
+}
+
+#[test]
+fn decodes_and_encodes() {
+    let url = "https://puzz.link/p?nurikabe/5/5/m5n2l4g";
+    let board = Board::from_pzpr(url).unwrap();
+    assert_eq!(board.to_string(), ".....\n..5..\n.....\n.2...\n...4.\n");
+    assert_eq!(board.pzpr().unwrap(), url);
+
+    // pzprv's flag segments before the dimensions are skipped
+    let flagged = Board::from_pzpr("https://puzz.link/p?nurikabe/v:/5/5/m5n2l4g").unwrap();
+    assert_eq!(flagged.b64(), board.b64());
+}
+
+#[test]
+fn round_trips_every_clue_width() {
+    let islands = [
+        island(0, 0, 15),
+        island(0, 2, 16),
+        island(1, 1, 4095),
+        island(2, 0, 4096),
+        island(2, 2, 12287),
+    ];
+    let board = Board::from_islands(3, 3, islands.into_iter());
+    let url = board.pzpr().unwrap();
+    assert_eq!(url, "https://puzz.link/p?nurikabe/3/3/fg-10g+fffg=000g%fff");
+    assert_eq!(Board::from_pzpr(&url).unwrap().b64(), board.b64());
+
+    // A run of more than 20 clueless cells takes more than one letter
+    let board = Board::from_islands(5, 5, [island(4, 3, 2)].into_iter());
+    let url = board.pzpr().unwrap();
+    assert_eq!(url, "https://puzz.link/p?nurikabe/5/5/zi2g");
+    assert_eq!(Board::from_pzpr(&url).unwrap().b64(), board.b64());
+}
+
+#[test]
+fn rejects_unencodable_clues() {
+    for n in [0, 12288] {
+        let board = Board::from_islands(1, 2, [island(0, 0, n)].into_iter());
+        assert_eq!(board.pzpr(), Err(PzprError::Unencodable(n)));
+    }
+}
+
+#[test]
+fn rejects_bad_bodies() {
+    use PzprError::*;
+
+    let decode = |body: &str| Board::from_pzpr(&format!("nurikabe/2/2/{}", body)).err();
+
+    assert_eq!(decode("1i"), None);
+    assert_eq!(decode("1h"), Some(TooFewCells));
+    assert_eq!(decode("1j"), Some(TooManyCells));
+    assert_eq!(decode("0i"), Some(BadClue(0)));
+    assert_eq!(decode("-1Ai"), Some(BadClue(0)));
+    assert_eq!(decode("g+12h"), Some(BadClue(1)));
+    assert_eq!(decode("g++12g"), Some(BadClue(1)));
+    assert_eq!(decode("-1"), Some(BadClue(0)));
+    assert_eq!(decode(".i"), Some(UnknownClue(0)));
+    assert_eq!(decode("Gh"), Some(BadClue(0)));
+}
@@ -0,0 +1,27 @@
+use nurikabe::*;
+
+// A clue of 0 can be typed into the entry screen, but no island fits it
+#[test]
+fn zero_clue_has_no_solutions() {
+    let board = Board::from_islands(3, 3, [Island { r: 1, c: 1, n: 0 }].into_iter());
+    assert_eq!(count_solutions(&board, 2), 0);
+    assert!(certify(&board).agrees_with(&solve(&board)));
+}
+
+#[test]
+fn counting_gives_up_when_cancelled() {
+    let board: Board = ".....
+                        ..5..
+                        .....
+                        .2...
+                        ...4."
+        .parse()
+        .unwrap();
+    assert!(count_solutions(&board, 2) > 1);
+
+    let cancel = CancelToken::new();
+    cancel.cancel();
+    let mut solutions = solutions(&board).within(&SolverConfig::new().cancel_with(cancel));
+    assert!(solutions.next().is_none());
+    assert_eq!(solutions.stopped(), Some(Stop::Cancelled));
+}
@@ -0,0 +1,98 @@
+use nurikabe::*;
+
+fn board(text: &str) -> Board {
+    text.parse().unwrap()
+}
+
+// island_contra tries each shape of an island in turn. A shape which couldn't be decided either way
+// used to count as a solution, so a later shape which was solved made this board look ambiguous
+#[test]
+fn undecided_shape_is_not_a_solution() {
+    let board = board(
+        "...2
+         ....
+         ....
+         .4..",
+    );
+
+    let solution = solve(&board);
+    assert!(solution.solved);
+    assert_eq!(solution.unique, Some(true));
+    assert!(solution.states.last().unwrap().violations().is_empty());
+}
+
+// Once the known water is all one sea, guess tries each independent region of the board on its
+// own. Guessing in the whole board at once (as it does when already focused) has to come to the
+// same conclusions
+#[test]
+fn regions_agree_with_the_whole_board() {
+    for text in [
+        ".5...
+         ...5.
+         .....",
+        "...
+         .3.
+         ...
+         .5.
+         ...",
+        "...2...
+         .......
+         3.....4",
+    ] {
+        let board = board(text);
+        let split = solve_with_limits(&board, 3);
+
+        let mut known = Knowledge::new(&board);
+        known.focus(&board.iter().map(|(c, _)| c).collect());
+        known.depth_limit = 0;
+        known.raise_max = Some(3);
+        let whole = solve_knowing(&mut known);
+
+        let verdict = |s: &Solution| (s.solved, s.unique, s.contradiction);
+        assert_eq!(verdict(&split), verdict(&whole), "{}", board);
+        assert!(split.solved);
+        assert!(split.states.last().unwrap().is_solution());
+        assert!(whole.states.last().unwrap().is_solution());
+    }
+}
+
+// When the land branch of a guess was solved with more than one solution, guess used to make the
+// cell water instead, and ended up claiming this puzzle (which has 16 solutions) had none
+#[test]
+fn guess_keeps_the_branch_it_solved() {
+    let board = board(
+        ".....
+         ..5..
+         .....
+         .2...
+         ...4.",
+    );
+
+    let solution = solve_with_limits(&board, 3);
+    assert!(!solution.contradiction);
+    assert!(solution.solved);
+    assert_eq!(solution.unique, Some(false));
+    assert!(brute_force(&board).contains(solution.states.last().unwrap()));
+}
+
+#[test]
+fn solver_steps_are_the_proof() {
+    let board = board(
+        "...2
+         ....
+         ....
+         .4..",
+    );
+    let solution = solve(&board);
+
+    let steps: Vec<Step> = Solver::new(&board).collect();
+    assert_eq!(steps.len(), solution.reasons.len());
+    for (i, step) in steps.iter().enumerate() {
+        assert_eq!(step.reason, solution.reasons[i]);
+        assert_eq!(step.board, solution.states[i + 1]);
+    }
+
+    // Stopping early leaves the same steps behind
+    let first: Vec<Reason> = Solver::new(&board).take(5).map(|s| s.reason).collect();
+    assert_eq!(first, solution.reasons[..5]);
+}
@@ -0,0 +1,43 @@
+use nurikabe::*;
+
+fn board() -> Board {
+    "3...\n...#\n.2o.\n".parse().unwrap()
+}
+
+#[test]
+fn every_symmetry_has_the_same_slug() {
+    let board = board();
+    let slug = board.canonical_slug();
+
+    let mut seen = Set::default();
+    for t in 0..SYMMETRIES {
+        let transformed = board.transform(t);
+        assert!(seen.insert(transformed.b64()), "symmetry {} repeats", t);
+        assert_eq!(transformed.canonical_slug(), slug, "symmetry {}", t);
+        assert_eq!(transformed.canonical_hash(), board.canonical_hash());
+    }
+}
+
+#[test]
+fn slug_ignores_progress_but_not_clues() {
+    let board = board();
+    assert_eq!(board.clues().canonical_slug(), board.canonical_slug());
+
+    let other: Board = "3...\n....\n..2.\n".parse().unwrap();
+    assert_ne!(other.canonical_slug(), board.canonical_slug());
+}
+
+#[test]
+fn transforms_keep_clues_on_their_cells() {
+    let board = board();
+    for t in 0..SYMMETRIES {
+        let transformed = board.transform(t);
+        assert_eq!(transformed.islands.len(), 2);
+        for island in &transformed.islands {
+            assert_eq!(
+                transformed.lookup_island((island.r, island.c)),
+                Some(*island)
+            );
+        }
+    }
+}
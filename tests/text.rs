@@ -0,0 +1,57 @@
+use nurikabe::*;
+
+fn round_trip(board: &Board) {
+    let text = board.to_string();
+    let back: Board = text
+        .parse()
+        .unwrap_or_else(|e| panic!("{:?} doesn't parse: {}", text, e));
+    assert_eq!(back.b64(), board.b64(), "{:?}", text);
+}
+
+#[test]
+fn round_trips_small_clues() {
+    let mut board = Board::from_islands(2, 3, [Island { r: 0, c: 0, n: 2 }].into_iter());
+    board.tiles[2] = Tile::Water;
+    board.tiles[4] = Tile::Land;
+    assert_eq!(board.to_string(), "2.#\n.o.\n");
+    round_trip(&board);
+}
+
+#[test]
+fn round_trips_wide_clues() {
+    let board = Board::from_islands(2, 2, [Island { r: 1, c: 0, n: 12 }].into_iter());
+    assert_eq!(board.to_string(), " .  .\n12  .\n");
+    round_trip(&board);
+}
+
+#[test]
+fn round_trips_a_single_column() {
+    let board = Board::from_islands(2, 1, [Island { r: 0, c: 0, n: 12 }].into_iter());
+    assert_eq!(board.to_string(), "12\n .\n");
+    round_trip(&board);
+
+    let islands = [Island { r: 0, c: 0, n: 12 }, Island { r: 2, c: 0, n: 123 }];
+    round_trip(&Board::from_islands(3, 1, islands.into_iter()));
+}
+
+#[test]
+fn one_spaced_row_makes_the_board_spaced() {
+    let err = "2.\n1 .\n".parse::<Board>().err().unwrap();
+    assert_eq!((err.line, err.col), (1, 1));
+    assert_eq!(err.kind, ParseBoardErrorKind::BadCell("2.".to_string()));
+
+    // Indentation alone doesn't
+    let board: Board = "  2.\n  .3\n".parse().unwrap();
+    assert_eq!(board.dims, (2, 2));
+
+    // Nor do rows which are ragged either way
+    let err = "2.\n1\n".parse::<Board>().err().unwrap();
+    assert_eq!(err.line, 2);
+    assert!(matches!(
+        err.kind,
+        ParseBoardErrorKind::RaggedRow {
+            expected: 2,
+            found: 1
+        }
+    ));
+}
@@ -0,0 +1,59 @@
+use nurikabe::*;
+
+fn violations(text: &str) -> Vec<Violation> {
+    text.parse::<Board>().unwrap().violations()
+}
+
+#[test]
+fn unfinished_boards_have_no_violations() {
+    assert_eq!(violations("2..\n...\n..3\n"), vec![]);
+    // Too small, but it could still grow
+    assert_eq!(violations("2#.\n.#.\n..3\n"), vec![]);
+    // Unclued land that could still reach a clue
+    assert_eq!(violations("2..\n.o.\n..3\n"), vec![]);
+    // Seas that could still be joined through the empty cells
+    assert_eq!(violations("#.#\n.o.\n#.1\n"), vec![]);
+}
+
+#[test]
+fn certain_violations_are_reported_early() {
+    use Violation::*;
+
+    assert_eq!(
+        violations("##.\n##.\n..2\n"),
+        vec![Pool(vec![(0, 0), (1, 0), (0, 1), (1, 1)])]
+    );
+
+    let [WrongSize(island, cells)] = &violations("2o.\n.o.\n...\n")[..] else {
+        panic!("expected a single WrongSize");
+    };
+    assert_eq!(*island, Island { r: 0, c: 0, n: 2 });
+    assert_eq!(cells.len(), 3);
+
+    // Enclosed, so it can't grow any more
+    assert!(matches!(
+        &violations("3#.\n##.\n...\n")[..],
+        [WrongSize(Island { n: 3, .. }, _)]
+    ));
+    assert!(matches!(&violations("o#.\n#..\n..1\n")[..], [NoClue(_)]));
+    assert!(matches!(
+        &violations("2o1\n...\n...\n")[..],
+        [MultipleClues(_)]
+    ));
+
+    // Cut off by land, so the smaller sea can never join the larger one
+    assert_eq!(
+        violations("#o.\noo#\n.##\n"),
+        vec![DisconnectedSea(vec![(0, 0)])]
+    );
+}
+
+#[test]
+fn solutions_have_no_violations() {
+    let board: Board = "2o#\n##.\n1#.\n".parse().unwrap();
+    assert!(!board.is_solution());
+
+    let board: Board = "2o#\n###\n1#1\n".parse().unwrap();
+    assert!(board.violations().is_empty());
+    assert!(board.is_solution());
+}